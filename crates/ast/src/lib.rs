@@ -24,6 +24,35 @@ pub trait NodeExt<'tree>: Node<'tree> {
         std::iter::successors(Some(self.upcast()), Node::parent)
     }
 
+    /// Iterate over the current node and all of its descendants in pre-order
+    fn descendants(&self) -> impl Iterator<Item = UntypedNode<'tree>> + use<'tree, Self> {
+        let mut cursor = self.raw().walk();
+        let mut depth = 0usize;
+        let mut done = false;
+        std::iter::from_fn(move || {
+            if done {
+                return None;
+            }
+            let node = UntypedNode::from(cursor.node());
+            if cursor.goto_first_child() {
+                depth += 1;
+            } else {
+                loop {
+                    if depth == 0 {
+                        done = true;
+                        break;
+                    }
+                    if cursor.goto_next_sibling() {
+                        break;
+                    }
+                    cursor.goto_parent();
+                    depth -= 1;
+                }
+            }
+            Some(node)
+        })
+    }
+
     /// Checks if the node is a certain implementor of Node
     ///
     /// This is perhaps only useful for UntypedNode, and
@@ -93,4 +122,15 @@ impl<'tree> blade::Element<'tree> {
         });
         tag_name.ok()
     }
+
+    /// The name in the closing `</x-alert>`, if the element has one
+    pub fn end_tag_name(self) -> Option<blade::TagName<'tree>> {
+        let element = self;
+        let mut cursor = element.walk();
+        let end_tag = element
+            .children(&mut cursor)
+            .filter_map(Result::ok)
+            .find_map(|child| child.as_end_tag())?;
+        end_tag.tag_name().ok()
+    }
 }
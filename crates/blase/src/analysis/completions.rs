@@ -6,10 +6,19 @@ use smol_str::{SmolStr, ToSmolStr};
 use type_sitter::{Node, UntypedNode};
 
 use crate::{
-    config::Config,
+    config::{Config, WorkspaceRoot},
     db::{
         DocumentDatabase, FilePosition, ParsedDocument, RootDatabase, SourceDatabase,
-        def::{self, Component, ComponentAttr, DefDatabase, Directive, Layout, Name},
+        def::{
+            self, ArgumentKind, Component, ComponentAttr, ComponentName, DefDatabase, Directive,
+            Document, DocumentId, Layout, Name, ViewName,
+            attributes::node_range,
+            class_component::{ClassComponent, MemberKind},
+            inheritance::Template,
+            php::PhpFile,
+            slots::SlotFill,
+            stacks::Stacks,
+        },
         text_edit::TextEdit,
     },
 };
@@ -27,47 +36,212 @@ pub fn completions(
     position: FilePosition,
     trigger_char: Option<char>,
 ) -> Option<Vec<CompletionItem>> {
+    let root = config.root_for(&position.path);
     let document = db.parsed_document(&position.path)?;
-    let (ctx, analysis) = &CompletionContext::new(db, position, &document, trigger_char, config)?;
+    let document = ClassComponent::document_at(db, document, position.offset, root);
+    if let Some('\'' | '"') = trigger_char
+        && !quote_opens_completion(db, &document, position.offset)
+    {
+        return None;
+    }
+    let (ctx, analysis) = &CompletionContext::new(db, position, &document, trigger_char, root)?;
     let mut items: Vec<CompletionItem> = Vec::new();
     if let Some('{') = trigger_char {
         return complete_echo(ctx, analysis);
     }
+    let variable = variable_at(ctx);
 
     {
         let acc = &mut items;
+        if let Some(range) = variable {
+            variable_completion(acc, ctx, &document, root, range);
+        }
 
         match analysis {
             ContextAnalysis::Directive(_directive) => {
                 directive_completion(acc, ctx, analysis);
-                //TODO: collect data on what kind of completions are helpful in a directive parameter.
+                argument_completion(acc, ctx, root, analysis);
             }
             ContextAnalysis::Tag { kind } => attribute_completion(&mut items, ctx, kind),
+            ContextAnalysis::Slot(component, _) => slot_completion(acc, ctx, component, analysis),
+            // A variable does not start a tag
+            ContextAnalysis::Document { .. } if variable.is_some() => {}
             ContextAnalysis::Document { name } => {
                 directive_completion(acc, ctx, analysis);
                 let (start, name) = name.as_ref()?;
                 let start_offset = TextSize::from(*start);
-                component_completion(acc, ctx, start_offset, name, config, analysis);
-                layout_completion(acc, ctx, start_offset, name, config, analysis);
+                component_completion(acc, ctx, start_offset, name, root, analysis);
+                layout_completion(acc, ctx, start_offset, name, root, analysis);
             }
         }
     }
     Some(items)
 }
 
+/// Whether a quote typed at `offset` opens a string that has completions:
+/// an argument of a directive, such as a view name, or the value of a
+/// component attribute. Quotes open every HTML attribute and PHP string, so
+/// the rest are turned down before analysing the context.
+fn quote_opens_completion(db: &RootDatabase, document: &ParsedDocument, offset: TextSize) -> bool {
+    let Some(node) = document.get_node_at(offset) else {
+        return false;
+    };
+    if def::argument_at(node).is_some() {
+        return true;
+    }
+    node.ancestors()
+        .find_map(|node| node.downcast::<ast::blade::Attribute>().ok())
+        .and_then(|attr| attr.parent())
+        .and_then(|tag| {
+            ast::match_node!(tag, {
+                ast::blade::SelfClosingTag(tag) => tag.tag_name().ok(),
+                ast::blade::StartTag(tag) => tag.tag_name().ok(),
+                _ => None,
+            })
+        })
+        .and_then(|name| document.text_for_node(db, name))
+        .is_some_and(|name| name.starts_with("x-"))
+}
+
+fn argument_completion(
+    items: &mut Vec<CompletionItem>,
+    ctx: &CompletionContext,
+    root: &WorkspaceRoot,
+    analysis: &ContextAnalysis,
+) {
+    let Some((directive, kind, _)) = def::argument_at(ctx.node) else {
+        return;
+    };
+    let db = ctx.db;
+    let source_range = ctx.source_range(analysis);
+    let Some(contents) = db.contents(&ctx.position.path) else {
+        return;
+    };
+    let typed = &contents[TextRange::new(source_range.start(), ctx.position.offset)];
+    let names = match kind {
+        ArgumentKind::View => view_names(db, root),
+        ArgumentKind::Section => section_names(db, &ctx.position, root, directive),
+        ArgumentKind::Stack => stack_names(db, directive),
+    };
+    let completions = names
+        .into_iter()
+        .filter(|name| name.starts_with(typed))
+        .sorted()
+        .dedup()
+        .map(|name| CompletionItem {
+            label: name.clone(),
+            kind: CompletionItemKind::Snippet,
+            edit: TextEdit::replace(source_range, name.clone()),
+            source_range,
+            lookup: SmolStr::new(&name),
+            relevance: CompletionRelevance::default(),
+        });
+    items.extend(completions);
+}
+
+fn view_names(db: &RootDatabase, root: &WorkspaceRoot) -> Vec<String> {
+    db.all_documents()
+        .iter()
+        .filter_map(|doc| ViewName::from_document(db, doc, root))
+        .map(|name| name.as_str().to_owned())
+        .collect()
+}
+
+/// Sections yielded up the parent chain can be filled in with `@section`,
+/// while `@yield` renders the sections filled in by children.
+fn section_names(
+    db: &RootDatabase,
+    position: &FilePosition,
+    root: &WorkspaceRoot,
+    directive: Directive,
+) -> Vec<String> {
+    let Some(doc) = db.parsed_document(&position.path) else {
+        return Vec::new();
+    };
+    let mut names = Vec::new();
+    if matches!(
+        directive,
+        Directive::Section | Directive::HasSection | Directive::SectionMissing
+    ) {
+        let rendered = Template::ancestors(db, &doc, root)
+            .iter()
+            .flat_map(|ancestor| Template::rendered_sections(db, ancestor, root))
+            .map(|(_, site)| site.name.to_string());
+        names.extend(rendered);
+    }
+    if matches!(
+        directive,
+        Directive::Yield | Directive::HasSection | Directive::SectionMissing
+    ) {
+        let filled = Template::descendants(db, &doc, root)
+            .iter()
+            .flat_map(|child| Template::for_document(db, child).sections.clone())
+            .map(|site| site.name.to_string());
+        names.extend(filled);
+    }
+    names
+}
+
+/// `@push` offers the stacks rendered in the workspace, while `@stack`
+/// offers the ones that are pushed to.
+fn stack_names(db: &RootDatabase, directive: Directive) -> Vec<String> {
+    db.all_documents()
+        .iter()
+        .flat_map(|doc| {
+            let stacks = Stacks::for_document(db, doc);
+            if directive == Directive::Stack {
+                stacks.pushes.clone()
+            } else {
+                stacks.renders.clone()
+            }
+        })
+        .map(|site| site.name.to_string())
+        .collect()
+}
+
+fn slot_completion(
+    items: &mut Vec<CompletionItem>,
+    ctx: &CompletionContext,
+    component: &Component,
+    analysis: &ContextAnalysis,
+) {
+    let db = ctx.db;
+    let Some(slots) = component.signature(db).slots.clone() else {
+        return;
+    };
+    let source_range = ctx.source_range(analysis);
+    let Some(contents) = db.contents(&ctx.position.path) else {
+        return;
+    };
+    let typed = &contents[TextRange::new(source_range.start(), ctx.position.offset)];
+    let completions = slots
+        .named
+        .iter()
+        .filter(|name| name.as_str().starts_with(typed))
+        .map(|name| CompletionItem {
+            label: name.to_string(),
+            kind: CompletionItemKind::Snippet,
+            edit: TextEdit::replace(source_range, name.to_string()),
+            source_range,
+            lookup: SmolStr::new(name.as_str()),
+            relevance: CompletionRelevance::default(),
+        });
+    items.extend(completions);
+}
+
 fn layout_completion(
     items: &mut Vec<CompletionItem>,
     ctx: &CompletionContext,
     start_offset: TextSize,
     name: &Name,
-    config: &Config,
+    root: &WorkspaceRoot,
     analysis: &ContextAnalysis,
 ) {
     let name_range = TextRange::at(start_offset, TextSize::of(name.as_str()));
     let db = ctx.db;
     let all_docs = db.all_documents();
     let layouts = all_docs.into_iter().filter_map(|doc| {
-        let layout = Layout::from_document(db, doc, config)?;
+        let layout = Layout::from_document(db, doc, root)?;
         let lname = layout.name(db);
         if lname.starts_with(name) {
             Some(layout)
@@ -113,14 +287,14 @@ fn component_completion(
     ctx: &CompletionContext,
     start_offset: TextSize,
     name: &Name,
-    config: &Config,
+    root: &WorkspaceRoot,
     analysis: &ContextAnalysis,
 ) {
     let name_range = TextRange::at(start_offset, TextSize::of(name.as_str()));
     let db = ctx.db;
     let all_docs = db.all_documents();
     let components = all_docs.into_iter().filter_map(|doc| {
-        let component = Component::for_document(db, doc, config)?;
+        let component = Component::for_document(db, doc, root)?;
         let cname = component.name(db);
         if cname.inner().starts_with(name) {
             Some(component)
@@ -144,6 +318,8 @@ fn component_completion_item(
     let attributes = &signature.attrs;
     let component_name = &signature.name;
     let label = component_name.tag_name();
+    // Components rendering $slot take content, which is where the cursor ends up
+    let uses_slot = signature.slots.as_ref().is_some_and(|slots| slots.default);
 
     let mut builder = TextEdit::builder();
     builder.delete(name_range);
@@ -154,20 +330,20 @@ fn component_completion_item(
     if let Some(attributes) = attributes {
         let len = attributes.len();
         let mut i = 1;
-        for ComponentAttr {
-            name,
-            default_value: _,
-        } in attributes.iter()
-        {
-            macros::format_to!(buf, " {}=\"${}\"", name, i);
+        for attr in attributes.iter() {
+            macros::format_to!(buf, " {}=\"${}\"", attr.tag_name(), i);
             i += 1;
-            if i == len {
+            if i == len && !uses_slot {
                 i = 0;
             }
         }
     }
 
-    buf.push_str("/>");
+    if uses_slot {
+        macros::format_to!(buf, ">\n    $0\n</x-{}>", component_name);
+    } else {
+        buf.push_str("/>");
+    }
 
     builder.insert(name_range.start(), buf);
 
@@ -191,6 +367,10 @@ fn attribute_completion(items: &mut Vec<CompletionItem>, ctx: &CompletionContext
     let db = ctx.db;
     match tag {
         Tag::Component(component) => {
+            if let Some((passed, value_range)) = attribute_value_at(ctx) {
+                attribute_value_completion(items, ctx, component, passed, value_range);
+                return;
+            }
             let Some(attrs) = component.attrs(db).clone() else {
                 return;
             };
@@ -236,7 +416,7 @@ fn component_attribute_completion(
     let node = ctx.node;
     tracing::debug!(?node);
     let start_offset = ctx.position.offset;
-    let attr_name = attr.name.as_str();
+    let attr_name = &attr.tag_name();
     let mut insert_text = String::new();
     let mut builder = TextEdit::builder();
     if node.is::<ast::blade::AttributeName>() {
@@ -263,6 +443,109 @@ fn component_attribute_completion(
     }
 }
 
+/// The attribute whose quoted value holds the cursor, as in `type="$0"`,
+/// along with the range between its quotes
+fn attribute_value_at<'a>(
+    ctx: &CompletionContext<'a>,
+) -> Option<(ast::blade::Attribute<'a>, TextRange)> {
+    let offset = ctx.position.offset;
+    let attr = ctx
+        .node
+        .ancestors()
+        .find_map(|node| node.downcast::<ast::blade::Attribute>().ok())?;
+    let node = attr.upcast();
+    let mut cursor = node.walk();
+    let value = node.untyped_children(&mut cursor).find(|child| {
+        ast::node_is!(
+            *child,
+            ast::blade::QuotedAttributeValue | ast::blade::QuotedExpression
+        )
+    })?;
+    let range = node_range(value);
+    if range.len() < TextSize::of("\"\"") || !(range.start() < offset && offset < range.end()) {
+        return None;
+    }
+    let quote = TextSize::of('"');
+    Some((
+        attr,
+        TextRange::new(range.start() + quote, range.end() - quote),
+    ))
+}
+
+/// Completes the value of an attribute from the type of its constructor
+/// parameter: the cases of a backed enum, string literals of a docblock
+/// union and booleans
+fn attribute_value_completion(
+    items: &mut Vec<CompletionItem>,
+    ctx: &CompletionContext,
+    component: &Component,
+    passed: ast::blade::Attribute<'_>,
+    value_range: TextRange,
+) {
+    let db = ctx.db;
+    let Some(doc) = db.parsed_document(&ctx.position.path) else {
+        return;
+    };
+    let Some((name, _)) = ComponentAttr::passed_name(db, &doc, passed) else {
+        return;
+    };
+    let Some(attrs) = component.attrs(db) else {
+        return;
+    };
+    let Some(attr) = attrs.iter().find(|attr| attr.is_passed_as(name)) else {
+        return;
+    };
+    let php = PhpFile::for_document(db, &component.id().document(db));
+    let values = attr.values(db, &php);
+    let is_expression = matches!(passed, ast::blade::Attribute::ExpressionAttribute(_));
+
+    for value in &values.strings {
+        // An expression attribute takes PHP, in which the value is a string
+        let insert = match is_expression {
+            true => format!("'{value}'"),
+            false => value.to_string(),
+        };
+        items.push(CompletionItem {
+            label: value.to_string(),
+            kind: CompletionItemKind::Snippet,
+            edit: TextEdit::replace(value_range, insert),
+            source_range: value_range,
+            lookup: value.clone(),
+            relevance: CompletionRelevance::default(),
+        });
+    }
+    if !values.boolean {
+        return;
+    }
+    for value in ["true", "false"] {
+        let item = if is_expression {
+            CompletionItem {
+                label: value.to_owned(),
+                kind: CompletionItemKind::Snippet,
+                edit: TextEdit::replace(value_range, value.to_owned()),
+                source_range: value_range,
+                lookup: SmolStr::new(value),
+                relevance: CompletionRelevance::default(),
+            }
+        } else {
+            // `active="false"` passes the string "false", which is truthy,
+            // so booleans turn the attribute into an expression attribute.
+            // The closing quote is left in place.
+            let tag_name = attr.tag_name();
+            let range = TextRange::new(node_range(passed).start(), value_range.end());
+            CompletionItem {
+                label: format!(":{tag_name}=\"{value}\""),
+                kind: CompletionItemKind::Snippet,
+                edit: TextEdit::replace(range, format!(":{tag_name}=\"{value}")),
+                source_range: range,
+                lookup: SmolStr::new(format!("{tag_name}=\"{value}")),
+                relevance: CompletionRelevance::default(),
+            }
+        };
+        items.push(item);
+    }
+}
+
 fn directive_completion(
     items: &mut Vec<CompletionItem>,
     ctx: &CompletionContext,
@@ -479,6 +762,69 @@ fn complete_echo(
     Some(cmps)
 }
 
+/// The range of the variable being typed inside `{{ }}`, including its `$`
+fn variable_at(ctx: &CompletionContext) -> Option<TextRange> {
+    if !ctx
+        .node
+        .ancestors()
+        .any(|node| node.is::<ast::blade::PhpStatement>())
+    {
+        return None;
+    }
+    let contents = ctx.db.contents(&ctx.position.path)?;
+    let offset = usize::from(ctx.position.offset);
+    let is_name = |b: &u8| b.is_ascii_alphanumeric() || *b == b'_';
+    let before = contents.get(..offset)?;
+    let start = offset - before.bytes().rev().take_while(is_name).count();
+    if start == 0 || contents.as_bytes()[start - 1] != b'$' {
+        return None;
+    }
+    let end = offset + contents[offset..].bytes().take_while(is_name).count();
+    Some(TextRange::new(
+        TextSize::new(start as u32 - 1),
+        TextSize::new(end as u32),
+    ))
+}
+
+/// Completes the variables of a component template: the public properties
+/// and methods of the class component that renders it, e.g. `$title` and
+/// `$shout()`, followed by the `$attributes` and `$slot` of every component
+fn variable_completion(
+    items: &mut Vec<CompletionItem>,
+    ctx: &CompletionContext,
+    document: &ParsedDocument,
+    root: &WorkspaceRoot,
+    range: TextRange,
+) {
+    const COMPONENT_VARIABLES: [&str; 2] = ["attributes", "slot"];
+    let class = ClassComponent::for_template(ctx.db, document, root);
+    if class.is_none() && ComponentName::from_document(ctx.db, document, root).is_none() {
+        return;
+    }
+    let members = class
+        .iter()
+        .flat_map(|(_, component)| component.members.iter())
+        .map(|member| (member.name.clone(), member.kind));
+    let variables = COMPONENT_VARIABLES
+        .into_iter()
+        .map(|name| (SmolStr::new(name), MemberKind::Property));
+    let completions = members.chain(variables).map(|(name, kind)| {
+        let (label, insert) = match kind {
+            MemberKind::Property => (format!("${name}"), format!("${name}")),
+            MemberKind::Method => (format!("${name}()"), format!("${name}($0)")),
+        };
+        CompletionItem {
+            label,
+            kind: CompletionItemKind::Snippet,
+            edit: TextEdit::replace(range, insert),
+            source_range: range,
+            lookup: SmolStr::new(format!("${name}")),
+            relevance: CompletionRelevance::default(),
+        }
+    });
+    items.extend(completions);
+}
+
 #[derive(Clone)]
 pub struct CompletionContext<'a> {
     db: &'a RootDatabase,
@@ -514,6 +860,8 @@ pub enum ContextAnalysis<'a> {
     Directive(Directive),
     // The cursor is inside an element; specifically the element's start tag or self-closing tag
     Tag { kind: Tag<'a> },
+    // The cursor is after `<x-slot:` inside a component. Records where the slot name begins
+    Slot(def::Component, TextSize),
     // The cursor is in a Text or Document node.
     // If ident is Some, then the cursor is after an identifier and records the beginning offset of
     // that identifier and the identifier itself
@@ -526,6 +874,7 @@ impl<'a> std::fmt::Debug for ContextAnalysis<'a> {
         match self {
             Self::Directive(arg0) => f.debug_tuple("Directive").field(arg0).finish(),
             Self::Tag { kind } => f.debug_struct("Tag").field("kind", kind).finish(),
+            Self::Slot(_component, start) => f.debug_tuple("Slot").field(start).finish(),
             Self::Document { name: ident } => {
                 let mut f = f.debug_struct("Document");
                 let n: Option<String> = None;
@@ -567,20 +916,20 @@ impl<'a> Tag<'a> {
         db: &dyn DefDatabase,
         node: UntypedNode<'a>,
         doc: &ParsedDocument,
-        config: &Config,
+        root: &WorkspaceRoot,
     ) -> Option<Self> {
         let (tag_name, is_start) = ast::match_node!(node, {
             ast::blade::StartTag(tag) => (tag.tag_name().ok()?, true),
             ast::blade::SelfClosingTag(tag) => (tag.tag_name().ok()?, false),
-            ast::blade::Element(element) => return Self::determine(db, element.tag()?, doc, config),
+            ast::blade::Element(element) => return Self::determine(db, element.tag()?, doc, root),
             _ => return None,
         });
 
-        if let Some(component) = def::Component::for_tagname(db, tag_name, doc, config) {
+        if let Some(component) = def::Component::for_tagname(db, tag_name, doc, root) {
             return Some(Self::Component(component));
         }
 
-        if let Some(layout) = def::Layout::for_tagname(db, tag_name, doc, config) {
+        if let Some(layout) = def::Layout::for_tagname(db, tag_name, doc, root) {
             return Some(Self::Layout(layout));
         }
 
@@ -594,10 +943,25 @@ impl<'a> Tag<'a> {
     }
 }
 
-fn analyze_context<'db>(ctx: &CompletionContext<'db>, config: &Config) -> ContextAnalysis<'db> {
+fn analyze_context<'db>(
+    ctx: &CompletionContext<'db>,
+    root: &WorkspaceRoot,
+) -> ContextAnalysis<'db> {
     let FilePosition { ref path, offset } = ctx.position;
     let db = ctx.db;
 
+    if let Some((directive, _, _)) = def::argument_at(ctx.node) {
+        return ContextAnalysis::Directive(directive);
+    }
+
+    if let Some(contents) = db.contents(path)
+        && let Some(start) = slot_name_start(&contents, offset.into())
+        && let Some(document) = db.parsed_document(path)
+        && let Some(component) = SlotFill::component(db, &document, ctx.node, root)
+    {
+        return ContextAnalysis::Slot(component, start);
+    }
+
     let ancestors = ctx.node.ancestors();
     for ancestor in ancestors {
         if ancestor.is_error() {
@@ -621,7 +985,7 @@ fn analyze_context<'db>(ctx: &CompletionContext<'db>, config: &Config) -> Contex
         let document = &db
             .parsed_document(path)
             .expect("file does not exist. this is a bug");
-        if let Some(tag) = Tag::determine(db, ancestor, document, config) {
+        if let Some(tag) = Tag::determine(db, ancestor, document, root) {
             return ContextAnalysis::Tag { kind: tag };
         }
     }
@@ -629,6 +993,18 @@ fn analyze_context<'db>(ctx: &CompletionContext<'db>, config: &Config) -> Contex
     unreachable!()
 }
 
+/// Where the slot name begins if the cursor is right after `<x-slot:`
+/// and a partially typed name
+fn slot_name_start(contents: &str, offset: usize) -> Option<TextSize> {
+    const OPEN: &str = "<x-slot:";
+    let before = contents.get(..offset)?;
+    let start = before.rfind(OPEN)? + OPEN.len();
+    if !before[start..].bytes().all(is_slot_name) {
+        return None;
+    }
+    Some(TextSize::new(start as u32))
+}
+
 fn get_first_child(node: UntypedNode<'_>) -> Option<UntypedNode<'_>> {
     let mut cursor = node.walk();
     node.untyped_children(&mut cursor).next()
@@ -640,7 +1016,7 @@ impl<'db> CompletionContext<'db> {
         position @ FilePosition { path: _, offset }: FilePosition,
         doc: &'db ParsedDocument,
         trigger_char: Option<char>,
-        config: &Config,
+        root: &WorkspaceRoot,
     ) -> Option<(Self, ContextAnalysis<'db>)> {
         let mut node = doc.get_node_at(offset)?;
         if node.is::<ast::blade::Comment>() {
@@ -662,18 +1038,37 @@ impl<'db> CompletionContext<'db> {
             trigger_char,
             node,
         };
-        let analysis = analyze_context(&ctx, config);
+        let analysis = analyze_context(&ctx, root);
         Some((ctx, analysis))
     }
 
     pub fn source_range(&self, analysis: &ContextAnalysis) -> TextRange {
         let node = self.node;
+        if let ContextAnalysis::Slot(_, start) = analysis
+            && let Some(contents) = self.db.contents(&self.position.path)
+        {
+            let len = contents[usize::from(*start)..]
+                .bytes()
+                .take_while(|b| is_slot_name(*b))
+                .count();
+            return TextRange::at(*start, TextSize::new(len as u32));
+        }
+
         if ast::node_is!(node, ast::blade::TagName | ast::blade::AttributeName) {
             let start = node.start_byte() as u32;
             let end = node.end_byte() as u32;
             return TextRange::new(start.into(), end.into());
         }
 
+        if let ContextAnalysis::Directive(_) = analysis
+            && let Some((_, _, literal)) = def::argument_at(node)
+        {
+            // Only the contents of the string literal, without its quotes
+            let start = literal.start_byte() as u32 + 1;
+            let end = (literal.end_byte() as u32).saturating_sub(1).max(start);
+            return TextRange::new(start.into(), end.into());
+        }
+
         if let ContextAnalysis::Document { name: Some(ident) } = analysis {
             let (start, ident) = ident;
             return TextRange::at((*start).into(), (ident.as_str().len() as u32).into());
@@ -687,6 +1082,10 @@ fn is_ident(b: u8) -> bool {
     matches!(b, b'a'..=b'z' | b'A'..=b'Z' | b'0'..=b'9' | b'_' | b'-' | b':' | b'.' | b'@')
 }
 
+fn is_slot_name(b: u8) -> bool {
+    matches!(b, b'a'..=b'z' | b'A'..=b'Z' | b'0'..=b'9' | b'_' | b'-')
+}
+
 fn extract_ident(contents: &str, offset: usize) -> Option<(u32, &str)> {
     let (start, ident) = text_procs::match_in_offset(contents, offset, is_ident)?;
 
@@ -801,7 +1200,9 @@ fn test_relevance(is_directive_end: bool, is_after_switch: bool) {
 }
 
 pub const TRIGGER_CHARS: &[char] = &[
-    '@', // directives
-    '{', // echo statements
-    ':', // expression attributes
+    '@',  // directives
+    '{',  // echo statements
+    ':',  // expression attributes
+    '\'', // view names
+    '"',
 ];
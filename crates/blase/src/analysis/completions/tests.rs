@@ -14,11 +14,18 @@ use crate::{
 mod components_and_layouts;
 mod directives;
 mod echo;
+mod sections;
+mod stacks;
+mod views;
 
 const TEST_CONFIG: LazyLock<Config> = LazyLock::new(|| Config {
     capabilities: async_lsp::lsp_types::ClientCapabilities::default(),
-    workspace_folder: camino::Utf8PathBuf::from_path_buf(std::path::PathBuf::from("/")).unwrap(),
+    roots: vec![crate::config::WorkspaceRoot::new(
+        "test".to_string(),
+        camino::Utf8PathBuf::from_path_buf(std::path::PathBuf::from("/")).unwrap(),
+    )],
     client_info: None,
+    settings: Default::default(),
 });
 
 fn get_completion_items(blade_fixture: &str) -> Vec<CompletionItem> {
@@ -98,6 +98,40 @@ fn attribute_completion_edit() {
     );
 }
 
+#[test]
+fn camel_case_attribute_completion_edit() {
+    check_edit(
+        "due-date",
+        r#"
+//- /resources/views/components/badge.blade.php
+@props(['dueDate'])
+
+//- /resources/views/index.blade.php
+<x-badge d$0/>
+"#,
+        expect![[r#"
+            <x-badge due-date="$0"/>
+        "#]],
+    );
+}
+
+#[test]
+fn snake_case_attribute_completion_edit() {
+    check_edit(
+        "item_id",
+        r#"
+//- /resources/views/components/row.blade.php
+@props(['item_id'])
+
+//- /resources/views/index.blade.php
+<x-row i$0/>
+"#,
+        expect![[r#"
+            <x-row item_id="$0"/>
+        "#]],
+    );
+}
+
 #[test]
 fn attribute_completion() {
     check(
@@ -128,3 +162,262 @@ fn no_attribute_completion_in_end_tag() {
         expect![[""]],
     );
 }
+
+#[test]
+fn component_with_slot_completion_edit() {
+    check_edit(
+        "x-card",
+        r#"
+//- /resources/views/components/card.blade.php
+@props(['title', 'footer'])
+<h1>{{ $title }}</h1>
+{{ $slot }}
+
+//- /resources/views/index.blade.php
+c$0
+"#,
+        expect![[r#"
+            <x-card title="$1" footer="$2">
+                $0
+            </x-card>
+        "#]],
+    );
+}
+
+#[test]
+fn slot_name_completion() {
+    check(
+        r#"
+//- /resources/views/components/card.blade.php
+@if ($header->isNotEmpty())
+    <header>{{ $header }}</header>
+@endif
+{{ $slot }}
+<footer>{{ $footer }}</footer>
+
+//- /resources/views/index.blade.php
+<x-card>
+    <x-slot:$0
+</x-card>
+"#,
+        expect![[r#"
+            header
+            footer"#]],
+    );
+}
+
+#[test]
+fn slot_name_completion_skips_loop_variables() {
+    check(
+        r#"
+//- /resources/views/components/list.blade.php
+<h2>{{ $heading ?? '' }}</h2>
+@foreach ($items as $key => $item)
+    <li>{{ $key }}: {{ $item }}</li>
+@endforeach
+
+//- /resources/views/index.blade.php
+<x-list>
+    <x-slot:$0
+</x-list>
+"#,
+        expect![[r#"
+            heading"#]],
+    );
+}
+
+#[test]
+fn slot_name_completion_skips_class_members() {
+    check(
+        r#"
+//- /app/View/Components/Panel.php
+<?php
+
+class Panel extends Component
+{
+    public string $heading = 'Panel';
+
+    public function render()
+    {
+        return view('components.panel');
+    }
+
+    public function shout(): string
+    {
+        return 'Hey';
+    }
+}
+//- /resources/views/components/panel.blade.php
+<h2>{{ $heading }}</h2>
+<p>{{ $shout }}</p>
+<footer>{{ $footer }}</footer>
+
+//- /resources/views/index.blade.php
+<x-panel>
+    <x-slot:$0
+</x-panel>
+"#,
+        expect![[r#"
+            footer"#]],
+    );
+}
+
+#[test]
+fn slot_name_completion_edit() {
+    check_edit(
+        "footer",
+        r#"
+//- /resources/views/components/card.blade.php
+{{ $slot }}
+<footer>{{ $footer }}</footer>
+
+//- /resources/views/index.blade.php
+<x-card>
+    <x-slot:fo$0>Bye</x-slot>
+</x-card>
+"#,
+        expect![[r#"
+            <x-card>
+                <x-slot:footer>Bye</x-slot>
+            </x-card>
+        "#]],
+    );
+}
+
+#[test]
+fn nested_component_completion() {
+    check(
+        r#"
+//- /resources/views/components/accordion/index.blade.php
+<div>{{ $slot }}</div>
+//- /resources/views/components/tabs/tabs.blade.php
+<div>{{ $slot }}</div>
+
+//- /resources/views/index.blade.php
+acc$0
+"#,
+        expect!["x-accordion"],
+    );
+}
+
+#[test]
+fn component_completion_in_inline_template() {
+    check(
+        r#"
+//- /resources/views/components/accordion/index.blade.php
+<div>{{ $slot }}</div>
+//- /app/View/Components/Alert.php
+<?php
+
+class Alert extends Component
+{
+    public function render()
+    {
+        return <<<'blade'
+            <div>
+                acc$0
+            </div>
+        blade;
+    }
+}
+"#,
+        expect!["x-accordion"],
+    );
+}
+
+#[test]
+fn package_component_completion() {
+    check(
+        r#"
+//- /vendor/acme/courier/resources/views/components/alert.blade.php
+<div>{{ $slot }}</div>
+
+//- /resources/views/index.blade.php
+cour$0
+"#,
+        expect!["x-courier::alert"],
+    );
+}
+
+#[test]
+fn enum_attribute_value_completion() {
+    check(
+        r#"
+//- /app/Enums/AlertType.php
+<?php
+
+namespace App\Enums;
+
+enum AlertType: string
+{
+    case Info = 'info';
+    case Error = 'error';
+}
+//- /app/View/Components/Alert.php
+<?php
+
+namespace App\View\Components;
+
+use App\Enums\AlertType;
+
+class Alert extends Component
+{
+    public function __construct(public AlertType $type) {}
+}
+//- /resources/views/index.blade.php
+<x-alert type="$0"/>
+"#,
+        expect![[r#"
+            info
+            error"#]],
+    );
+}
+
+#[test]
+fn docblock_attribute_value_completion_edit() {
+    check_edit(
+        "warning",
+        r#"
+//- /app/View/Components/Alert.php
+<?php
+
+namespace App\View\Components;
+
+class Alert extends Component
+{
+    /**
+     * @param 'info'|'warning' $type
+     */
+    public function __construct(public string $type) {}
+}
+//- /resources/views/index.blade.php
+<x-alert type="$0"/>
+"#,
+        expect![[r#"
+            <x-alert type="warning"/>
+        "#]],
+    );
+}
+
+#[test]
+fn boolean_attribute_value_completion_edit() {
+    check_edit(
+        ":dismissible=\"false\"",
+        r#"
+//- /app/View/Components/Alert.php
+<?php
+
+namespace App\View\Components;
+
+class Alert extends Component
+{
+    public function __construct(public bool $dismissible = true) {}
+}
+//- /resources/views/index.blade.php
+<x-alert dismissible="$0"/>
+"#,
+        expect![[r#"
+            <x-alert :dismissible="false"/>
+        "#]],
+    );
+}
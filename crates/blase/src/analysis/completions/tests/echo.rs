@@ -103,3 +103,95 @@ fn no_complete_in_unescaped_php_statement() {
         expect![[""]],
     );
 }
+
+#[test]
+fn completes_class_component_members() {
+    check(
+        r#"
+//- /app/View/Components/Alert.php
+<?php
+
+namespace App\View\Components;
+
+class Alert extends Component
+{
+    public string $title = 'Heads up';
+
+    protected string $secret = '';
+
+    public static int $count = 0;
+
+    public function __construct(public string $type, private bool $debug = false) {}
+
+    public function isError(): bool
+    {
+        return $this->type === 'error';
+    }
+
+    public function render()
+    {
+        return view('components.shared.alert');
+    }
+}
+//- /resources/views/components/shared/alert.blade.php
+<div>{{ $t$0 }}</div>
+"#,
+        expect![[r#"
+            $title
+            $type
+            $isError()
+            $attributes
+            $slot"#]],
+    );
+    check_edit(
+        "$isError()",
+        r#"
+//- /app/View/Components/Alert.php
+<?php
+
+namespace App\View\Components;
+
+class Alert extends Component
+{
+    public function isError(): bool
+    {
+        return false;
+    }
+
+    public function render()
+    {
+        return view('components.alert');
+    }
+}
+//- /resources/views/components/alert.blade.php
+<div>{{ $is$0 }}</div>
+"#,
+        expect![[r#"
+            <div>{{ $isError($0) }}</div>
+        "#]],
+    );
+}
+
+#[test]
+fn completes_anonymous_component_variables() {
+    check(
+        r#"
+//- /resources/views/components/card.blade.php
+<div {{ $attributes }}>{{ $$0 }}</div>
+"#,
+        expect![[r#"
+            $attributes
+            $slot"#]],
+    );
+}
+
+#[test]
+fn no_component_variables_in_views() {
+    check(
+        r#"
+//- /resources/views/welcome.blade.php
+<div>{{ $$0 }}</div>
+"#,
+        expect![[""]],
+    );
+}
@@ -0,0 +1,113 @@
+use expect_test::expect;
+
+use super::*;
+
+#[test]
+fn sections_yielded_up_the_parent_chain() {
+    check(
+        r#"
+//- /resources/views/partials/head.blade.php
+<title>@yield('title')</title>
+//- /resources/views/layouts/base.blade.php
+@include('partials.head')
+@yield('body')
+//- /resources/views/layouts/app.blade.php
+@extends('layouts.base')
+@section('body')
+    @yield('content')
+    @section('sidebar')
+        Main sidebar
+    @show
+@endsection
+//- /resources/views/index.blade.php
+@extends('layouts.app')
+@section('$0')
+@endsection
+"#,
+        expect![[r#"
+            body
+            content
+            sidebar
+            title"#]],
+    );
+}
+
+#[test]
+fn section_names_filtered() {
+    check(
+        r#"
+//- /resources/views/layouts/app.blade.php
+@yield('content')
+@yield('scripts')
+@yield('sidebar')
+//- /resources/views/index.blade.php
+@extends('layouts.app')
+@section('s$0')
+@endsection
+"#,
+        expect![[r#"
+            scripts
+            sidebar"#]],
+    );
+}
+
+#[test]
+fn yield_lists_sections_of_children() {
+    check(
+        r#"
+//- /resources/views/layouts/app.blade.php
+@yield('$0')
+//- /resources/views/index.blade.php
+@extends('layouts.app')
+@section('content')
+    Home
+@endsection
+//- /resources/views/about.blade.php
+@extends('layouts.app')
+@section('about')
+    About
+@endsection
+"#,
+        expect![[r#"
+            about
+            content"#]],
+    );
+}
+
+#[test]
+fn has_section_lists_sections_of_children() {
+    check(
+        r#"
+//- /resources/views/layouts/app.blade.php
+@hasSection('nav$0')
+    <nav>@yield('navigation')</nav>
+@endif
+//- /resources/views/index.blade.php
+@extends('layouts.app')
+@section('navigation')
+    Links
+@endsection
+"#,
+        expect!["navigation"],
+    );
+}
+
+#[test]
+fn section_name_completion_edit() {
+    check_edit(
+        "content",
+        r#"
+//- /resources/views/layouts/app.blade.php
+@yield('content')
+//- /resources/views/index.blade.php
+@extends('layouts.app')
+@section('con$0')
+@endsection
+"#,
+        expect![[r#"
+            @extends('layouts.app')
+            @section('content')
+            @endsection
+        "#]],
+    );
+}
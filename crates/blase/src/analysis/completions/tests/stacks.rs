@@ -0,0 +1,57 @@
+use expect_test::expect;
+
+use super::*;
+
+#[test]
+fn rendered_stacks_in_push() {
+    check(
+        r#"
+//- /resources/views/layouts/app.blade.php
+@stack('styles')
+@stack('scripts')
+//- /resources/views/partials/footer.blade.php
+@stack('modals')
+//- /resources/views/index.blade.php
+@extends('layouts.app')
+@push('$0')
+@endpush
+"#,
+        expect![[r#"
+            modals
+            scripts
+            styles"#]],
+    );
+}
+
+#[test]
+fn rendered_stacks_in_push_if() {
+    check(
+        r#"
+//- /resources/views/layouts/app.blade.php
+@stack('styles')
+@stack('scripts')
+//- /resources/views/index.blade.php
+@pushIf($admin, 'sc$0')
+@endPushIf
+"#,
+        expect!["scripts"],
+    );
+}
+
+#[test]
+fn pushed_stacks_in_stack() {
+    check(
+        r#"
+//- /resources/views/layouts/app.blade.php
+@stack('$0')
+//- /resources/views/index.blade.php
+@push('scripts')
+@endpush
+@prepend('styles')
+@endprepend
+"#,
+        expect![[r#"
+            scripts
+            styles"#]],
+    );
+}
@@ -0,0 +1,108 @@
+use expect_test::expect;
+
+use super::*;
+
+#[test]
+fn view_names_in_include() {
+    check(
+        r#"
+//- /resources/views/partials/header.blade.php
+<header></header>
+//- /resources/views/partials/footer.blade.php
+<footer></footer>
+//- /resources/views/components/alert.blade.php
+<div></div>
+//- /resources/views/index.blade.php
+@include('$0')
+"#,
+        expect![[r#"
+            index
+            partials.footer
+            partials.header"#]],
+    );
+}
+
+#[test]
+fn view_names_filtered_by_segments() {
+    check(
+        r#"
+//- /resources/views/partials/header.blade.php
+<header></header>
+//- /resources/views/partials/footer.blade.php
+<footer></footer>
+//- /resources/views/layouts/app.blade.php
+@yield('content')
+//- /resources/views/index.blade.php
+@includeWhen($admin, 'partials.f$0')
+"#,
+        expect!["partials.footer"],
+    );
+}
+
+#[test]
+fn view_names_in_include_first() {
+    check(
+        r#"
+//- /resources/views/layouts/app.blade.php
+@yield('content')
+//- /resources/views/index.blade.php
+@includeFirst(['custom.admin', 'lay$0'])
+"#,
+        expect!["layouts.app"],
+    );
+}
+
+#[test]
+fn view_names_in_extends() {
+    check(
+        r#"
+//- /resources/views/layouts/app.blade.php
+@yield('content')
+//- /resources/views/index.blade.php
+@extends("layouts.$0")
+"#,
+        expect!["layouts.app"],
+    );
+}
+
+#[test]
+fn no_view_names_in_other_arguments() {
+    check(
+        r#"
+//- /resources/views/partials/header.blade.php
+<header></header>
+//- /resources/views/index.blade.php
+@include('partials.header', ['title' => 'p$0'])
+"#,
+        expect![""],
+    );
+}
+
+#[test]
+fn view_name_completion_edit() {
+    check_edit(
+        "partials.header",
+        r#"
+//- /resources/views/partials/header.blade.php
+<header></header>
+//- /resources/views/index.blade.php
+@include('partials.h$0')
+"#,
+        expect![[r#"
+            @include('partials.header')
+        "#]],
+    );
+}
+
+#[test]
+fn no_view_names_in_html_attributes() {
+    check(
+        r#"
+//- /resources/views/partials/header.blade.php
+<header></header>
+//- /resources/views/index.blade.php
+<div class="$0"></div>
+"#,
+        expect![""],
+    );
+}
@@ -1,13 +1,21 @@
+use ast::NodeExt;
 use camino::Utf8Path;
+use itertools::Itertools;
 use line_index::{TextRange, TextSize};
 use tree_sitter::{Query, QueryCursor, StreamingIterator};
 use type_sitter::{HasChildren, Node, UntypedNode};
 
 use crate::{
-    config::Config,
+    config::{Config, WorkspaceRoot},
     db::{
         DocumentDatabase, FileRange, ParsedDocument, RootDatabase, Severity,
-        def::{ComponentName, LayoutName, queries},
+        def::{
+            Component, ComponentName, Directive, LayoutName, attributes::ComponentUsages,
+            class_component::ClassComponent, inheritance::Template, queries, slots::SlotFill,
+            stacks::Stacks, views::ViewReference,
+        },
+        source_change::SourceChange,
+        text_edit::TextEdit,
     },
     resolve_path,
     util::FileType,
@@ -20,10 +28,26 @@ pub struct Diagnostic {
     pub message: String,
     pub range: FileRange,
     pub severity: Severity,
+    pub fix: Option<Fix>,
 }
 
-pub fn syntax_errors(db: &RootDatabase, path: &Utf8Path) -> Vec<Diagnostic> {
-    let errors = db.parse_errors(path);
+/// A quick fix that resolves a diagnostic
+pub struct Fix {
+    pub label: String,
+    pub source_change: SourceChange,
+}
+
+/// Blade syntax errors. In a PHP file these are only the errors in the
+/// template a class component writes inline, PHP itself is left to PHP tools.
+pub fn syntax_errors(db: &RootDatabase, root: &WorkspaceRoot, path: &Utf8Path) -> Vec<Diagnostic> {
+    let errors = match db.parsed_document(path) {
+        Some(document) if document.filetype == FileType::PHP => {
+            ClassComponent::inline_template(db, &document, root)
+                .map(|template| template.parse_errors(db))
+                .unwrap_or_default()
+        }
+        _ => db.parse_errors(path),
+    };
     errors
         .into_iter()
         .map(|e| {
@@ -50,6 +74,7 @@ pub fn syntax_errors(db: &RootDatabase, path: &Utf8Path) -> Vec<Diagnostic> {
                     range: text_range,
                 },
                 severity: Severity::Error,
+                fix: None,
             }
         })
         .collect()
@@ -57,24 +82,36 @@ pub fn syntax_errors(db: &RootDatabase, path: &Utf8Path) -> Vec<Diagnostic> {
 
 /// Request both syntax and semantic diagnostics for the given [`Utf8Path`].
 pub fn full_diagnostics(db: &RootDatabase, config: &Config, path: &Utf8Path) -> Vec<Diagnostic> {
-    let mut syntax_errors = syntax_errors(db, path);
-    let semantic_errors = semantic_diagnostics(db, config, path);
+    let root = config.root_for(path);
+    let mut syntax_errors = syntax_errors(db, root, path);
+    let semantic_errors = semantic_diagnostics(db, root, path);
     syntax_errors.extend(semantic_errors);
     syntax_errors
 }
 
 pub fn semantic_diagnostics(
     db: &RootDatabase,
-    config: &Config,
+    root: &WorkspaceRoot,
     path: &Utf8Path,
 ) -> Vec<Diagnostic> {
     let mut acc = Vec::new();
     let Some(document) = db.parsed_document(path) else {
         return acc;
     };
-    if document.filetype == FileType::Blade {
-        no_such_component_or_layout(db, &document, config, &mut acc);
-    }
+    let document = match document.filetype {
+        FileType::Blade => document,
+        FileType::PHP => match ClassComponent::inline_template(db, &document, root) {
+            Some(template) => template,
+            None => return acc,
+        },
+    };
+    no_such_component_or_layout(db, &document, root, &mut acc);
+    no_such_slot(db, &document, root, &mut acc);
+    no_such_view(db, &document, root, &mut acc);
+    section_not_yielded(db, &document, root, &mut acc);
+    stack_not_rendered(db, &document, root, &mut acc);
+    missing_required_attributes(db, &document, root, &mut acc);
+    unknown_attributes(db, &document, root, &mut acc);
     acc
 }
 
@@ -104,7 +141,7 @@ impl<'tree> Element<'tree> {
 fn no_such_component_or_layout<'tree>(
     db: &RootDatabase,
     document: &ParsedDocument,
-    config: &Config,
+    root: &WorkspaceRoot,
     acc: &mut Vec<Diagnostic>,
 ) {
     let contents = document.contents(db);
@@ -139,7 +176,7 @@ fn no_such_component_or_layout<'tree>(
                 );
 
                 let (class_path, resources_path) =
-                    resolve_path::component_paths(component_name, config);
+                    resolve_path::component_paths(db, component_name, root);
 
                 if ![class_path, resources_path]
                     .iter()
@@ -149,6 +186,7 @@ fn no_such_component_or_layout<'tree>(
                         message,
                         range,
                         severity,
+                        fix: None,
                     });
                 }
                 continue;
@@ -160,7 +198,8 @@ fn no_such_component_or_layout<'tree>(
                     layout_name.tag_name()
                 );
 
-                let (class_path, resources_path) = resolve_path::layout_paths(layout_name, config);
+                let (class_path, resources_path) =
+                    resolve_path::layout_paths(db, layout_name, root);
                 if ![class_path, resources_path]
                     .iter()
                     .any(|path| db.parsed_document(path).is_some())
@@ -169,6 +208,7 @@ fn no_such_component_or_layout<'tree>(
                         message,
                         range,
                         severity,
+                        fix: None,
                     });
                 }
                 continue;
@@ -176,3 +216,283 @@ fn no_such_component_or_layout<'tree>(
         }
     }
 }
+
+fn no_such_slot(
+    db: &RootDatabase,
+    document: &ParsedDocument,
+    root: &WorkspaceRoot,
+    acc: &mut Vec<Diagnostic>,
+) {
+    let elements = document
+        .root_node()
+        .descendants()
+        .filter_map(|node| node.downcast::<ast::blade::Element>().ok());
+    for element in elements {
+        let Some(fill) = SlotFill::for_element(db, document, element) else {
+            continue;
+        };
+        let Some(component) = SlotFill::component(db, document, element.upcast(), root) else {
+            continue;
+        };
+        let signature = component.signature(db);
+        // Without the component's template there is nothing to check against
+        let Some(slots) = &signature.slots else {
+            continue;
+        };
+        if slots.contains(&fill.name) {
+            continue;
+        }
+        let message = format!(
+            "component `{}` has no slot named `{}`",
+            signature.name.tag_name(),
+            fill.name
+        );
+        acc.push(Diagnostic {
+            message,
+            range: FileRange {
+                path: document.source.path(db).to_owned(),
+                range: fill.range,
+            },
+            severity: Severity::Warning,
+            fix: None,
+        });
+    }
+}
+
+fn no_such_view(
+    db: &RootDatabase,
+    document: &ParsedDocument,
+    root: &WorkspaceRoot,
+    acc: &mut Vec<Diagnostic>,
+) {
+    let directives = document
+        .root_node()
+        .descendants()
+        .filter(|node| node.is::<ast::blade::InlineDirective>());
+    for directive in directives {
+        let references = ViewReference::for_directive(db, document, directive);
+        let (found, missing): (Vec<_>, Vec<_>) = references
+            .into_iter()
+            .partition(|reference| reference.resolve(db, root).is_some());
+        for reference in missing {
+            match reference.directive {
+                // @includeIf silently skips views that do not exist
+                Directive::IncludeIf => continue,
+                // @includeFirst only fails when none of the candidates exist
+                Directive::IncludeFirst if !found.is_empty() => continue,
+                _ => {}
+            }
+            let message = format!(
+                "cannot find view `{}` in the current workspace",
+                reference.name.as_str()
+            );
+            acc.push(Diagnostic {
+                message,
+                range: FileRange {
+                    path: document.source.path(db).to_owned(),
+                    range: reference.range,
+                },
+                severity: Severity::Error,
+                fix: None,
+            });
+        }
+    }
+}
+
+fn section_not_yielded(
+    db: &RootDatabase,
+    document: &ParsedDocument,
+    root: &WorkspaceRoot,
+    acc: &mut Vec<Diagnostic>,
+) {
+    let template = Template::for_document(db, document);
+    // Without a complete chain of layouts we can't tell what gets rendered
+    if template.extends.is_none() || !Template::is_chain_complete(db, document, root) {
+        return;
+    }
+    let rendered = Template::ancestors(db, document, root)
+        .iter()
+        .flat_map(|ancestor| Template::rendered_sections(db, ancestor, root))
+        .map(|(_, site)| site.name)
+        .collect::<Vec<_>>();
+    for section in &template.sections {
+        // A section ending in @show renders itself
+        if rendered.contains(&section.name) || template.yields.contains(section) {
+            continue;
+        }
+        let message = format!(
+            "section `{}` is not yielded by any parent view",
+            section.name
+        );
+        acc.push(Diagnostic {
+            message,
+            range: FileRange {
+                path: document.source.path(db).to_owned(),
+                range: section.range,
+            },
+            severity: Severity::Warning,
+            fix: None,
+        });
+    }
+}
+
+fn stack_not_rendered(
+    db: &RootDatabase,
+    document: &ParsedDocument,
+    root: &WorkspaceRoot,
+    acc: &mut Vec<Diagnostic>,
+) {
+    let stacks = Stacks::for_document(db, document);
+    for push in &stacks.pushes {
+        let Some(rendered) = Stacks::rendered_around(db, document, root, push) else {
+            continue;
+        };
+        if rendered.contains(&push.name) {
+            continue;
+        }
+        let message = format!("stack `{}` is not rendered by any layout", push.name);
+        acc.push(Diagnostic {
+            message,
+            range: FileRange {
+                path: document.source.path(db).to_owned(),
+                range: push.range,
+            },
+            severity: Severity::Warning,
+            fix: None,
+        });
+    }
+}
+
+fn missing_required_attributes(
+    db: &RootDatabase,
+    document: &ParsedDocument,
+    root: &WorkspaceRoot,
+    acc: &mut Vec<Diagnostic>,
+) {
+    let path = document.source.path(db);
+    for usage in &ComponentUsages::for_document(db, document).usages {
+        // Forwarding the attribute bag may pass anything
+        if usage.forwards_attributes {
+            continue;
+        }
+        let Some(component) = ComponentName::new(&format!("x-{}", usage.name))
+            .and_then(|name| Component::for_name(db, &name, root))
+        else {
+            continue;
+        };
+        let signature = component.signature(db);
+        // A slot fills in a prop of an anonymous component, whereas a class
+        // component is constructed before its slots are rendered
+        let slots = match component.is_anonymous(db) {
+            true => usage.slots.as_slice(),
+            false => Default::default(),
+        };
+        let missing = signature
+            .attrs
+            .iter()
+            .flat_map(|attrs| attrs.iter())
+            .filter(|attr| attr.required)
+            .filter(|attr| {
+                usage
+                    .attrs
+                    .iter()
+                    .map(|passed| &passed.name)
+                    .chain(slots)
+                    .all(|passed| !attr.is_passed_as(passed.as_str()))
+            })
+            .collect::<Vec<_>>();
+        if missing.is_empty() {
+            continue;
+        }
+        let list = missing
+            .iter()
+            .map(|attr| format!("`{}`", attr.tag_name()))
+            .join(", ");
+        let (message, label) = match missing.len() {
+            1 => (
+                format!(
+                    "component `{}` is missing the required attribute {list}",
+                    signature.name.tag_name()
+                ),
+                format!("Add the missing attribute {list}"),
+            ),
+            _ => (
+                format!(
+                    "component `{}` is missing the required attributes {list}",
+                    signature.name.tag_name()
+                ),
+                "Add the missing attributes".to_owned(),
+            ),
+        };
+        let inserted = missing
+            .iter()
+            .map(|attr| match attr.takes_string() {
+                true => format!(" {}=\"\"", attr.tag_name()),
+                // Anything else has to be passed as an expression
+                false => format!(" :{}=\"\"", attr.tag_name()),
+            })
+            .collect::<String>();
+        let mut source_change = SourceChange::default();
+        source_change.insert_source_edit(
+            path.to_owned(),
+            TextEdit::insert(usage.tag_range.end(), inserted),
+        );
+        acc.push(Diagnostic {
+            message,
+            range: FileRange {
+                path: path.to_owned(),
+                range: usage.tag_range,
+            },
+            severity: Severity::Error,
+            fix: Some(Fix {
+                label,
+                source_change,
+            }),
+        });
+    }
+}
+
+fn unknown_attributes(
+    db: &RootDatabase,
+    document: &ParsedDocument,
+    root: &WorkspaceRoot,
+    acc: &mut Vec<Diagnostic>,
+) {
+    let path = document.source.path(db);
+    for usage in &ComponentUsages::for_document(db, document).usages {
+        let Some(component) = ComponentName::new(&format!("x-{}", usage.name))
+            .and_then(|name| Component::for_name(db, &name, root))
+        else {
+            continue;
+        };
+        let signature = component.signature(db);
+        // Anything the component doesn't declare ends up in `$attributes`
+        if signature.forwards_attributes {
+            continue;
+        }
+        let declared = signature.attrs.as_deref().unwrap_or_default();
+        for passed in &usage.attrs {
+            if passed.name.as_str() == "attributes"
+                || declared
+                    .iter()
+                    .any(|attr| attr.is_passed_as(passed.name.as_str()))
+            {
+                continue;
+            }
+            let message = format!(
+                "component `{}` has no attribute named `{}`",
+                signature.name.tag_name(),
+                passed.name
+            );
+            acc.push(Diagnostic {
+                message,
+                range: FileRange {
+                    path: path.to_owned(),
+                    range: passed.range,
+                },
+                severity: Severity::Warning,
+                fix: None,
+            });
+        }
+    }
+}
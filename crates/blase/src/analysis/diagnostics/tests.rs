@@ -10,8 +10,12 @@ use crate::{
 
 const TEST_CONFIG: LazyLock<Config> = LazyLock::new(|| Config {
     capabilities: async_lsp::lsp_types::ClientCapabilities::default(),
-    workspace_folder: camino::Utf8PathBuf::from_path_buf(std::path::PathBuf::from("/")).unwrap(),
+    roots: vec![crate::config::WorkspaceRoot::new(
+        "test".to_string(),
+        camino::Utf8PathBuf::from_path_buf(std::path::PathBuf::from("/")).unwrap(),
+    )],
     client_info: None,
+    settings: Default::default(),
 });
 
 fn check_diagnostic(fixture: &str, expect: Expect) {
@@ -74,6 +78,22 @@ fn check_no_diagnostic(fixture: &str) {
     }
 }
 
+/// Applies the fix of the diagnostic under the cursor
+fn check_fix(fixture: &str, expect: Expect) {
+    let (analysis, position) = fixture::position(fixture);
+    let fix = analysis
+        .full_diagnostics(&TEST_CONFIG, &position.path)
+        .unwrap()
+        .into_iter()
+        .find(|d| d.range.range.contains_inclusive(position.offset))
+        .and_then(|d| d.fix)
+        .expect("no fix under the cursor");
+
+    let mut actual = analysis.contents(&position.path).unwrap().to_owned();
+    fix.source_change.source_file_edits[&position.path].apply(&mut actual);
+    expect.assert_eq(&actual);
+}
+
 #[test]
 fn slots() {
     check_no_diagnostic(
@@ -89,6 +109,138 @@ Hi {{ $name }}
     );
 }
 
+#[test]
+fn no_such_slot() {
+    check_diagnostic(
+        r#"
+//- /resources/views/components/card.blade.php
+<div>{{ $slot }}</div>
+@if ($footer->isNotEmpty())
+    <footer>{{ $footer }}</footer>
+@endif
+
+//- /resources/views/index.blade.php
+<x-card>
+    <x-slot:footr>Bye</x-slot:footr>
+    <x-slot name="header">Hi</x-slot>
+</x-card>
+"#,
+        expect![[r#"
+            [
+              /resources/views/index.blade.php (21..26): warning: component `x-card` has no slot named `footr`,
+              /resources/views/index.blade.php (64..70): warning: component `x-card` has no slot named `header`,
+            ]"#]],
+    );
+}
+
+#[test]
+fn named_slots_exist() {
+    check_no_diagnostic(
+        r#"
+//- /resources/views/components/card.blade.php
+@props(['title'])
+<h1>{{ $title }}</h1>
+{{ $slot }}
+@if ($footer->isNotEmpty())
+    <footer>{{ $footer }}</footer>
+@endif
+
+//- /resources/views/index.blade.php
+<x-card title="Greeting">
+    Hello
+    <x-slot:footer>Bye</x-slot:footer>
+</x-card>
+"#,
+    );
+}
+
+#[test]
+fn props_filled_by_slots() {
+    check_no_diagnostic(
+        r#"
+//- /resources/views/components/card.blade.php
+@props(['title'])
+<h1>{{ $title }}</h1>
+{{ $slot }}
+
+//- /resources/views/index.blade.php
+<x-card>
+    <x-slot:title>Hi</x-slot:title>
+</x-card>
+"#,
+    );
+}
+
+#[test]
+fn kebab_case_slot_names() {
+    check_no_diagnostic(
+        r#"
+//- /resources/views/components/card.blade.php
+{{ $slot }}
+<footer>{{ $footerLinks }}</footer>
+
+//- /resources/views/index.blade.php
+<x-card>
+    <x-slot:footer-links>Bye</x-slot:footer-links>
+</x-card>
+"#,
+    );
+}
+
+#[test]
+fn optional_slots() {
+    check_no_diagnostic(
+        r#"
+//- /resources/views/components/card.blade.php
+<header>{{ $header ?? '' }}</header>
+@isset($aside)
+    <aside>{!! $aside !!}</aside>
+@endisset
+@if ($footer)
+    <footer>{{ $footer }}</footer>
+@endif
+{{ Str::upper($title) }}
+
+//- /resources/views/index.blade.php
+<x-card>
+    <x-slot:header>Hi</x-slot:header>
+    <x-slot:aside>Aside</x-slot:aside>
+    <x-slot:footer>Bye</x-slot:footer>
+    <x-slot:title>Card</x-slot:title>
+</x-card>
+"#,
+    );
+}
+
+#[test]
+fn slots_of_the_view_a_class_component_renders() {
+    check_diagnostic(
+        r#"
+//- /app/View/Components/Panel.php
+<?php
+
+class Panel extends Component
+{
+    public function render()
+    {
+        return View::make('shared.panel');
+    }
+}
+//- /resources/views/shared/panel.blade.php
+<div {{ $attributes }}>{{ $slot }}</div>
+<footer>{{ $footer }}</footer>
+//- /resources/views/index.blade.php
+<x-panel class="wide">
+    <x-slot:footr>Bye</x-slot:footr>
+</x-panel>
+"#,
+        expect![[r#"
+            [
+              /resources/views/index.blade.php (35..40): warning: component `x-panel` has no slot named `footr`,
+            ]"#]],
+    );
+}
+
 #[test]
 fn no_such_component() {
     check_diagnostic(
@@ -102,6 +254,136 @@ fn no_such_component() {
     );
 }
 
+#[test]
+fn nested_anonymous_components_exist() {
+    check_no_diagnostic(
+        r#"
+//- /resources/views/components/accordion/index.blade.php
+<div>{{ $slot }}</div>
+//- /resources/views/components/tabs/tabs.blade.php
+<div>{{ $slot }}</div>
+//- /resources/views/components/tabs/tab.blade.php
+<div>{{ $slot }}</div>
+//- /resources/views/index.blade.php
+<x-accordion>
+    <x-tabs>
+        <x-tabs.tab>Hello</x-tabs.tab>
+    </x-tabs>
+</x-accordion>
+"#,
+    );
+}
+
+#[test]
+fn package_components_exist() {
+    check_no_diagnostic(
+        r#"
+//- /app/Providers/AppServiceProvider.php
+<?php
+
+namespace App\Providers;
+
+use Acme\Courier\Button;
+use Illuminate\Support\Facades\Blade;
+use Illuminate\Support\ServiceProvider;
+
+class AppServiceProvider extends ServiceProvider
+{
+    public function boot(): void
+    {
+        Blade::componentNamespace('Nightshade\\Views\\Components', 'nightshade');
+        Blade::anonymousComponentPath(__DIR__.'/../../resources/views/admin', 'admin');
+        $this->loadViewComponentsAs('courier', [Button::class]);
+    }
+}
+//- /vendor/nightshade/ui/src/Views/Components/Calendar.php
+<?php
+
+namespace Nightshade\Views\Components;
+
+class Calendar extends Component {}
+//- /vendor/acme/courier/src/Button.php
+<?php
+
+namespace Acme\Courier;
+
+class Button extends Component {}
+//- /vendor/acme/courier/resources/views/components/alert.blade.php
+<div>{{ $slot }}</div>
+//- /resources/views/admin/panel.blade.php
+<div>{{ $slot }}</div>
+//- /resources/views/index.blade.php
+<x-nightshade::calendar/>
+<x-admin::panel>Settings</x-admin::panel>
+<x-courier-button/>
+<x-courier::alert>Sent</x-courier::alert>
+"#,
+    );
+}
+
+#[test]
+fn aliased_components_exist() {
+    check_no_diagnostic(
+        r#"
+//- /app/Providers/AppServiceProvider.php
+<?php
+
+namespace App\Providers;
+
+use Illuminate\Support\Facades\Blade;
+use Illuminate\Support\ServiceProvider;
+
+class AppServiceProvider extends ServiceProvider
+{
+    public function boot(): void
+    {
+        Blade::component('alert', \App\Support\Alert::class);
+        Blade::component('App\\Support\\Badge', 'pill');
+        Blade::components(['App\\Support\\Card' => 'panel'], 'ui');
+    }
+}
+//- /app/Support/Alert.php
+<?php
+
+namespace App\Support;
+
+class Alert extends Component {}
+//- /app/Support/Badge.php
+<?php
+
+namespace App\Support;
+
+class Badge extends Component {}
+//- /app/Support/Card.php
+<?php
+
+namespace App\Support;
+
+class Card extends Component {}
+//- /resources/views/index.blade.php
+<x-alert/>
+<x-pill/>
+<x-ui-panel/>
+"#,
+    );
+}
+
+#[test]
+fn no_such_package_component() {
+    check_diagnostic(
+        r#"
+//- /vendor/acme/courier/resources/views/components/alert.blade.php
+<div>{{ $slot }}</div>
+//- /resources/views/index.blade.php
+<x-courier::toast/>
+"#,
+        expect![[r#"
+            [
+              /resources/views/index.blade.php (1..18): error: cannot find component `x-courier::toast` in the current workspace,
+            ]"#]],
+    );
+}
+
 #[test]
 fn no_such_layout() {
     check_diagnostic(
@@ -127,3 +409,585 @@ fn syntax_error() {
             ]"#]],
     );
 }
+
+#[test]
+fn syntax_error_in_inline_template() {
+    check_diagnostic(
+        r#"
+//- /app/View/Components/Alert.php
+<?php
+
+class Alert extends Component
+{
+    public function render()
+    {
+        return <<<'blade'
+            @if(match) @endif
+        blade;
+    }
+}
+"#,
+        expect![[r#"
+            [
+              /app/View/Components/Alert.php (112..121): error: Unexpected token(s): '@if (',
+            ]"#]],
+    );
+}
+
+#[test]
+fn no_such_component_in_inline_template() {
+    check_diagnostic(
+        r#"
+//- /app/View/Components/Alert.php
+<?php
+
+class Alert extends Component
+{
+    public function render()
+    {
+        return <<<'blade'
+            <div>
+                <x-foo/>
+            </div>
+        blade;
+    }
+}
+"#,
+        expect![[r#"
+            [
+              /app/View/Components/Alert.php (135..140): error: cannot find component `x-foo` in the current workspace,
+            ]"#]],
+    );
+}
+
+#[test]
+fn slots_of_inline_template() {
+    check_diagnostic(
+        r#"
+//- /app/View/Components/Alert.php
+<?php
+
+class Alert extends Component
+{
+    public function render()
+    {
+        return <<<'blade'
+            <div>{{ $slot }}</div>
+            <footer>{{ $footer }}</footer>
+        blade;
+    }
+}
+//- /resources/views/index.blade.php
+<x-alert>
+    <x-slot:footr>Bye</x-slot:footr>
+</x-alert>
+"#,
+        expect![[r#"
+            [
+              /resources/views/index.blade.php (22..27): warning: component `x-alert` has no slot named `footr`,
+            ]"#]],
+    );
+}
+
+#[test]
+fn missing_required_attributes_in_inline_template() {
+    check_diagnostic(
+        r#"
+//- /resources/views/components/card.blade.php
+@props(['title'])
+//- /app/View/Components/Alert.php
+<?php
+
+class Alert extends Component
+{
+    public function render()
+    {
+        return <<<'blade'
+            <x-card/>
+        blade;
+    }
+}
+"#,
+        expect![[r#"
+            [
+              /app/View/Components/Alert.php (113..119): error: component `x-card` is missing the required attribute `title`,
+            ]"#]],
+    );
+}
+
+#[test]
+fn unknown_attributes_in_inline_template() {
+    check_diagnostic(
+        r#"
+//- /resources/views/components/card.blade.php
+@props(['title'])
+//- /app/View/Components/Alert.php
+<?php
+
+class Alert extends Component
+{
+    public function render()
+    {
+        return <<<'blade'
+            <x-card title="Hi" titel="Hi"/>
+        blade;
+    }
+}
+"#,
+        expect![[r#"
+            [
+              /app/View/Components/Alert.php (131..136): warning: component `x-card` has no attribute named `titel`,
+            ]"#]],
+    );
+}
+
+#[test]
+fn stack_not_rendered_in_inline_template() {
+    check_diagnostic(
+        r#"
+//- /resources/views/components/layout.blade.php
+@stack('scripts')
+{{ $slot }}
+//- /app/View/Components/Alert.php
+<?php
+
+class Alert extends Component
+{
+    public function render()
+    {
+        return <<<'blade'
+            <x-layout>
+                @push('styles')
+                    <link rel="stylesheet" href="alert.css">
+                @endpush
+            </x-layout>
+        blade;
+    }
+}
+"#,
+        expect![[r#"
+            [
+              /app/View/Components/Alert.php (145..153): warning: stack `styles` is not rendered by any layout,
+            ]"#]],
+    );
+}
+
+#[test]
+fn section_not_yielded_in_inline_template() {
+    check_diagnostic(
+        r#"
+//- /resources/views/layouts/app.blade.php
+<main>@yield('content')</main>
+//- /app/View/Components/Alert.php
+<?php
+
+class Alert extends Component
+{
+    public function render()
+    {
+        return <<<'blade'
+            @extends('layouts.app')
+            @section('sidebar')
+                Links
+            @endsection
+        blade;
+    }
+}
+"#,
+        expect![[r#"
+            [
+              /app/View/Components/Alert.php (157..166): warning: section `sidebar` is not yielded by any parent view,
+            ]"#]],
+    );
+}
+
+#[test]
+fn no_such_view() {
+    check_diagnostic(
+        r#"
+//- /resources/views/index.blade.php
+@include('partials.header')
+"#,
+        expect![[r#"
+            [
+              /resources/views/index.blade.php (9..26): error: cannot find view `partials.header` in the current workspace,
+            ]"#]],
+    );
+}
+
+#[test]
+fn included_views_exist() {
+    check_no_diagnostic(
+        r#"
+//- /resources/views/partials/header.blade.php
+<header></header>
+//- /resources/views/admin.blade.php
+Admin
+//- /resources/views/index.blade.php
+@include('partials.header')
+@includeIf('partials.footer')
+@includeFirst(['custom.admin', 'admin'])
+@includeWhen($admin, 'admin')
+"#,
+    );
+}
+
+#[test]
+fn include_first_with_no_candidates() {
+    check_diagnostic(
+        r#"
+@includeFirst(['custom.admin', 'admin'])
+"#,
+        expect![[r#"
+            [
+              /index.blade.php (15..29): error: cannot find view `custom.admin` in the current workspace,
+              /index.blade.php (31..38): error: cannot find view `admin` in the current workspace,
+            ]"#]],
+    );
+}
+
+#[test]
+fn computed_view_names() {
+    check_no_diagnostic(
+        r#"
+//- /resources/views/index.blade.php
+@include($compact ? 'cards.small' : 'cards.large')
+@include('partials.' . $name)
+@include(view_name('x'))
+@includeFirst([$theme . '.header', 'missing' . $suffix])
+"#,
+    );
+}
+
+#[test]
+fn section_not_yielded() {
+    check_diagnostic(
+        r#"
+//- /resources/views/layouts/app.blade.php
+<main>@yield('content')</main>
+//- /resources/views/index.blade.php
+@extends('layouts.app')
+@section('sidebar')
+    Links
+@endsection
+"#,
+        expect![[r#"
+            [
+              /resources/views/index.blade.php (33..42): warning: section `sidebar` is not yielded by any parent view,
+            ]"#]],
+    );
+}
+
+#[test]
+fn sections_yielded_by_ancestors() {
+    check_no_diagnostic(
+        r#"
+//- /resources/views/partials/head.blade.php
+<title>@yield('title')</title>
+//- /resources/views/layouts/base.blade.php
+@include('partials.head')
+@yield('body')
+//- /resources/views/layouts/app.blade.php
+@extends('layouts.base')
+@section('body')
+    @section('sidebar')
+        Main sidebar
+    @show
+@endsection
+//- /resources/views/index.blade.php
+@extends('layouts.app')
+@section('title')
+    Home
+@endsection
+@section('sidebar')
+    @parent
+@endsection
+"#,
+    );
+}
+
+#[test]
+fn stack_not_rendered() {
+    check_diagnostic(
+        r#"
+//- /resources/views/layouts/app.blade.php
+@stack('scripts')
+//- /resources/views/index.blade.php
+@extends('layouts.app')
+@push('styles')
+    <link rel="stylesheet" href="app.css">
+@endpush
+"#,
+        expect![[r#"
+            [
+              /resources/views/index.blade.php (30..38): warning: stack `styles` is not rendered by any layout,
+            ]"#]],
+    );
+}
+
+#[test]
+fn push_next_to_a_component_in_a_partial() {
+    check_no_diagnostic(
+        r#"
+//- /resources/views/components/card.blade.php
+<div>{{ $slot }}</div>
+//- /resources/views/partials/chart.blade.php
+<x-card>Chart</x-card>
+@push('scripts')
+    <script src="chart.js"></script>
+@endpush
+"#,
+    );
+}
+
+#[test]
+fn stacks_rendered_by_layouts() {
+    check_no_diagnostic(
+        r#"
+//- /resources/views/partials/scripts.blade.php
+@stack('scripts')
+//- /resources/views/layouts/app.blade.php
+@include('partials.scripts')
+@yield('content')
+//- /resources/views/components/layout.blade.php
+@stack('styles')
+{{ $slot }}
+//- /resources/views/index.blade.php
+@extends('layouts.app')
+@push('scripts')
+    <script src="index.js"></script>
+@endpush
+//- /resources/views/about.blade.php
+<x-layout>
+    @push('styles')
+        <link rel="stylesheet" href="about.css">
+    @endpush
+</x-layout>
+//- /resources/views/components/alert.blade.php
+@push('scripts')
+    <script src="alert.js"></script>
+@endpush
+"#,
+    );
+}
+
+#[test]
+fn missing_required_attributes() {
+    check_diagnostic(
+        r#"
+//- /app/View/Components/Alert.php
+<?php
+
+namespace App\View\Components;
+
+class Alert extends Component
+{
+    public function __construct(
+        public string $type,
+        public ?string $title,
+        public string|null $icon,
+        public string $message = 'Saved',
+    ) {}
+}
+//- /resources/views/components/card.blade.php
+@props(['title', 'subtitle', 'footer' => null])
+//- /resources/views/index.blade.php
+<x-alert/>
+<x-alert type="info"/>
+<x-alert :attributes="$attributes"/>
+<x-card :$title></x-card>
+<x-card/>
+"#,
+        expect![[r#"
+            [
+              /resources/views/index.blade.php (1..8): error: component `x-alert` is missing the required attribute `type`,
+              /resources/views/index.blade.php (72..78): error: component `x-card` is missing the required attribute `subtitle`,
+              /resources/views/index.blade.php (98..104): error: component `x-card` is missing the required attributes `title`, `subtitle`,
+            ]"#]],
+    );
+}
+
+#[test]
+fn required_props_filled_by_slots() {
+    check_diagnostic(
+        r#"
+//- /resources/views/components/card.blade.php
+@props(['title', 'subtitle'])
+<h1>{{ $title }}</h1>
+<h2>{{ $subtitle }}</h2>
+//- /resources/views/index.blade.php
+<x-card>
+    <x-slot:title>Hi</x-slot:title>
+</x-card>
+"#,
+        expect![[r#"
+            [
+              /resources/views/index.blade.php (1..7): error: component `x-card` is missing the required attribute `subtitle`,
+            ]"#]],
+    );
+}
+
+#[test]
+fn required_attributes_the_container_cannot_resolve() {
+    check_diagnostic(
+        r#"
+//- /app/Enums/Status.php
+<?php
+
+namespace App\Enums;
+
+enum Status: string
+{
+    case Active = 'active';
+}
+//- /app/Repositories/UserRepository.php
+<?php
+
+namespace App\Repositories;
+
+class UserRepository {}
+//- /app/View/Components/Alert.php
+<?php
+
+namespace App\View\Components;
+
+use App\Enums\Status;
+use App\Repositories\UserRepository;
+use Vendor\Types\Priority;
+
+class Alert extends Component
+{
+    public function __construct(
+        public UserRepository $users,
+        public Status $status,
+        public Priority $priority,
+        public $level,
+    ) {}
+}
+//- /resources/views/index.blade.php
+<x-alert/>
+<x-alert {{ $attributes }}/>
+<x-alert {{ $attributes->merge(['class' => 'mt-4']) }}/>
+"#,
+        expect![[r#"
+            [
+              /resources/views/index.blade.php (1..8): error: component `x-alert` is missing the required attributes `status`, `priority`, `level`,
+            ]"#]],
+    );
+}
+
+#[test]
+fn unknown_attributes() {
+    check_diagnostic(
+        r#"
+//- /resources/views/components/badge.blade.php
+@props(['dueDate', 'color' => 'gray'])
+<span>{{ $dueDate }}</span>
+//- /resources/views/components/button.blade.php
+@props(['type' => 'button'])
+<button {{ $attributes->merge(['type' => $type]) }}>{{ $slot }}</button>
+//- /resources/views/index.blade.php
+<x-badge due-date="today" colour="red"/>
+<x-button class="primary" :type="$type">Save</x-button>
+"#,
+        expect![[r#"
+            [
+              /resources/views/index.blade.php (26..32): warning: component `x-badge` has no attribute named `colour`,
+            ]"#]],
+    );
+}
+
+#[test]
+fn snake_case_is_not_camel_case() {
+    check_diagnostic(
+        r#"
+//- /resources/views/components/row.blade.php
+@props(['itemId' => null])
+<tr>{{ $itemId }}</tr>
+//- /resources/views/index.blade.php
+<x-row item-id="1"/>
+<x-row itemId="2"/>
+<x-row item_id="3"/>
+"#,
+        expect![[r#"
+            [
+              /resources/views/index.blade.php (48..55): warning: component `x-row` has no attribute named `item_id`,
+            ]"#]],
+    );
+}
+
+#[test]
+fn add_missing_attributes() {
+    check_fix(
+        r#"
+//- /resources/views/components/card.blade.php
+@props(['title', 'subtitle', 'footer' => null])
+//- /resources/views/index.blade.php
+<x-ca$0rd footer="Bye">
+    Hello
+</x-card>
+"#,
+        expect![[r#"
+            <x-card title="" subtitle="" footer="Bye">
+                Hello
+            </x-card>
+        "#]],
+    );
+}
+
+#[test]
+fn add_missing_expression_attributes() {
+    check_fix(
+        r#"
+//- /app/Enums/Status.php
+<?php
+
+namespace App\Enums;
+
+enum Status: string
+{
+    case Active = 'active';
+}
+//- /app/View/Components/Alert.php
+<?php
+
+namespace App\View\Components;
+
+use App\Enums\Status;
+
+class Alert extends Component
+{
+    public function __construct(
+        public string $message,
+        public bool $dismissible,
+        public int $count,
+        public Status $status,
+    ) {}
+}
+//- /resources/views/index.blade.php
+<x-al$0ert/>
+"#,
+        expect![[r#"
+            <x-alert message="" :dismissible="" :count="" :status=""/>
+        "#]],
+    );
+}
+
+#[test]
+fn add_missing_attribute_label() {
+    let (analysis, position) = fixture::position(
+        r#"
+//- /resources/views/components/card.blade.php
+@props(['title', 'footer' => null])
+//- /resources/views/index.blade.php
+<x-ca$0rd/>
+"#,
+    );
+    let labels = analysis
+        .full_diagnostics(&TEST_CONFIG, &position.path)
+        .unwrap()
+        .into_iter()
+        .filter_map(|d| Some(d.fix?.label))
+        .collect::<Vec<_>>();
+    assert_eq!(labels, ["Add the missing attribute `title`"]);
+}
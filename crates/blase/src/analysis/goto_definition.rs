@@ -1,10 +1,17 @@
+use ast::NodeExt;
+use line_index::TextSize;
 use type_sitter::{HasChild, Node, UntypedNode};
 
 use crate::{
-    config::Config,
+    config::{Config, WorkspaceRoot},
     db::{
-        DocumentDatabase, FilePosition, FileRange,
-        def::{ComponentName, LayoutName},
+        DocumentDatabase, FilePosition, FileRange, ParsedDocument,
+        def::{
+            ComponentName, LayoutName, Name, attributes,
+            class_component::ClassComponent,
+            inheritance::{SectionSite, Template},
+            views::ViewReference,
+        },
     },
     resolve_path,
 };
@@ -17,64 +24,179 @@ pub fn goto_definition(
     config: &Config,
     FilePosition { path, offset }: FilePosition,
 ) -> Option<Vec<FileRange>> {
-    let document = db.parsed_document(&path)?;
+    let root = config.root_for(&path);
+    let document = ClassComponent::document_at(db, db.parsed_document(&path)?, offset, root);
     let contents = &db.contents(&path)?;
     let node = document.get_node_at(offset)?;
     tracing::debug!(node = node.kind(), path = path.as_str());
-    goto_def(db, contents, config, node)
+    if let Some(ranges) = goto_def_for_section(db, &document, root, offset) {
+        return Some(ranges);
+    }
+    goto_def(db, &document, contents, root, node)
 }
 
 fn goto_def<'tree>(
     db: &dyn DocumentDatabase,
+    document: &ParsedDocument,
     contents: &str,
-    config: &Config,
+    root: &WorkspaceRoot,
     node: UntypedNode,
 ) -> Option<Vec<FileRange>> {
     ast::match_node!(node, {
-        ast::blade::TagName(tag_name) => goto_def_for_component(db, config, tag_name, contents),
+        ast::blade::TagName(tag_name) => goto_def_for_component(db, root, tag_name, contents),
         ast::blade::StartTag(start_tag) => {
-            goto_def_for_component(db, config, start_tag.tag_name().ok()?, contents)
+            goto_def_for_component(db, root, start_tag.tag_name().ok()?, contents)
         },
         ast::blade::EndTag(end_tag) => {
             let tag_name = end_tag.child().ok()?;
-            goto_def_for_component(db, config, tag_name, contents)
+            goto_def_for_component(db, root, tag_name, contents)
         },
         ast::blade::SelfClosingTag(self_tag) => {
-            goto_def_for_component(db, config, self_tag.tag_name().ok()?, contents)
+            goto_def_for_component(db, root, self_tag.tag_name().ok()?, contents)
         },
         _ => {
+            if let Some(reference) = ViewReference::for_node(db, document, node) {
+                return goto_def_for_view(db, root, reference);
+            }
+            if let Some(range) = goto_def_for_member(db, document, root, node) {
+                return Some(vec![range]);
+            }
             tracing::error!(node=node.kind(), "No component found");
             None
         },
     })
 }
 
-#[tracing::instrument(skip(db, config))]
+#[tracing::instrument(skip(db, root))]
 fn goto_def_for_component(
     db: &dyn DocumentDatabase,
-    config: &Config,
+    root: &WorkspaceRoot,
     tag_name: ast::blade::TagName,
     contents: &str,
 ) -> Option<Vec<FileRange>> {
     let name = contents.get(tag_name.byte_range())?;
-    let (class_path, resources_path) = if let Some(layout) = LayoutName::new(name) {
-        resolve_path::layout_paths(&layout, config)
+    let paths = if let Some(layout) = LayoutName::new(name) {
+        let (class_path, resources_path) = resolve_path::layout_paths(db, &layout, root);
+        vec![class_path, resources_path]
     } else {
-        resolve_path::component_paths(&ComponentName::new(name)?, config)
+        let name = ComponentName::new(name)?;
+        let (class_path, _) = resolve_path::component_paths(db, &name, root);
+        // The template a class component renders may live anywhere
+        let template = resolve_path::component_template(db, &name, root);
+        std::iter::once(class_path).chain(template).collect()
     };
-    let ranges = vec![class_path, resources_path]
+    let ranges = paths
         .into_iter()
         .filter_map(|path| {
-            if db.parsed_document(&path).is_some() {
-                let range = FileRange {
-                    path,
-                    range: Default::default(),
-                };
-                Some(range)
-            } else {
-                None
-            }
+            let document = db.parsed_document(&path)?;
+            let range = FileRange {
+                path,
+                range: attributes::definition_range(&document),
+            };
+            Some(range)
         })
         .collect();
     Some(ranges)
 }
+
+#[tracing::instrument(skip(db, root))]
+fn goto_def_for_view(
+    db: &dyn DocumentDatabase,
+    root: &WorkspaceRoot,
+    reference: ViewReference,
+) -> Option<Vec<FileRange>> {
+    let path = reference.path(db, root);
+    db.parsed_document(&path)?;
+    let range = FileRange {
+        path,
+        range: Default::default(),
+    };
+    Some(vec![range])
+}
+
+/// Jumps from `$title` in the template of a class component to the public
+/// property or method of the class
+fn goto_def_for_member(
+    db: &dyn DocumentDatabase,
+    document: &ParsedDocument,
+    root: &WorkspaceRoot,
+    node: UntypedNode,
+) -> Option<FileRange> {
+    let variable = node
+        .ancestors()
+        .find(|node| node.is::<ast::blade::VariableName>())?;
+    let name = document.text_for_node(db, variable)?.strip_prefix('$')?;
+    let (class, component) = ClassComponent::for_template(db, document, root)?;
+    let member = component.member(name)?;
+    Some(FileRange {
+        path: class.source.path(db).to_owned(),
+        range: member.range,
+    })
+}
+
+/// Jumps between a section and where it is rendered in the inheritance chain
+fn goto_def_for_section(
+    db: &dyn DocumentDatabase,
+    document: &ParsedDocument,
+    root: &WorkspaceRoot,
+    offset: TextSize,
+) -> Option<Vec<FileRange>> {
+    let template = Template::for_document(db, document);
+    let at_offset = |sites: &[SectionSite]| -> Option<Name> {
+        sites
+            .iter()
+            .find(|site| site.range.contains_inclusive(offset))
+            .map(|site| site.name.clone())
+    };
+    let to_range = |doc: &ParsedDocument, site: &SectionSite| FileRange {
+        path: doc.source.path(db).to_owned(),
+        range: site.range,
+    };
+
+    if let Some(name) = at_offset(&template.parents) {
+        // @parent renders the section of the nearest ancestor that defines it
+        return Template::ancestors(db, document, root)
+            .into_iter()
+            .map(|ancestor| {
+                Template::for_document(db, &ancestor)
+                    .sections
+                    .iter()
+                    .filter(|site| site.name == name)
+                    .map(|site| to_range(&ancestor, site))
+                    .collect::<Vec<_>>()
+            })
+            .find(|ranges| !ranges.is_empty());
+    }
+
+    let section = at_offset(&template.sections);
+    let yielded = at_offset(&template.yields);
+    if section.is_none() && yielded.is_none() {
+        return None;
+    }
+
+    let mut ranges = Vec::new();
+    if let Some(name) = section {
+        for ancestor in Template::ancestors(db, document, root) {
+            let rendered = Template::rendered_sections(db, &ancestor, root);
+            ranges.extend(
+                rendered
+                    .iter()
+                    .filter(|(_, site)| site.name == name)
+                    .map(|(doc, site)| to_range(doc, site)),
+            );
+        }
+    }
+    if let Some(name) = yielded {
+        for descendant in Template::descendants(db, document, root) {
+            let child = Template::for_document(db, &descendant);
+            ranges.extend(
+                child
+                    .sections
+                    .iter()
+                    .filter(|site| site.name == name)
+                    .map(|site| to_range(&descendant, site)),
+            );
+        }
+    }
+    Some(ranges)
+}
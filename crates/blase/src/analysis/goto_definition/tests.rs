@@ -2,20 +2,31 @@ use std::sync::LazyLock;
 
 use expect_test::{Expect, expect};
 
-use crate::{analysis::fixture, config::Config};
+use crate::{
+    analysis::fixture,
+    config::{Config, ProjectLayout, WorkspaceRoot},
+};
 
 use super::FileRange;
 
 const TEST_CONFIG: LazyLock<Config> = LazyLock::new(|| Config {
     capabilities: async_lsp::lsp_types::ClientCapabilities::default(),
-    workspace_folder: camino::Utf8PathBuf::from_path_buf(std::path::PathBuf::from("/")).unwrap(),
+    roots: vec![crate::config::WorkspaceRoot::new(
+        "test".to_string(),
+        camino::Utf8PathBuf::from_path_buf(std::path::PathBuf::from("/")).unwrap(),
+    )],
     client_info: None,
+    settings: Default::default(),
 });
 
 fn check(blase_fixture: &str, expect: Expect) {
+    check_with_config(&TEST_CONFIG, blase_fixture, expect);
+}
+
+fn check_with_config(config: &Config, blase_fixture: &str, expect: Expect) {
     let (analysis, position) = fixture::position(blase_fixture);
     let ranges = analysis
-        .goto_def(&TEST_CONFIG, position)
+        .goto_def(config, position)
         .expect("salsa cancelled");
 
     let mut actual = String::new();
@@ -48,6 +59,188 @@ fn component_in_nested_directory() {
     );
 }
 
+#[test]
+fn component_in_configured_view_root() {
+    let config = Config {
+        capabilities: async_lsp::lsp_types::ClientCapabilities::default(),
+        roots: vec![TEST_CONFIG.roots[0].clone().with_layout(ProjectLayout {
+            view_roots: vec![
+                "modules/admin/resources/views".into(),
+                "resources/views".into(),
+            ],
+            ..Default::default()
+        })],
+        client_info: None,
+        settings: Default::default(),
+    };
+    check_with_config(
+        &config,
+        r#"
+//- /modules/admin/resources/views/components/panel.blade.php
+<div>{{ $slot }}</div>
+//- /resources/views/index.blade.php
+<x-pa$0nel/>
+"#,
+        expect![[r#"
+            /modules/admin/resources/views\components\panel.blade.php
+        "#]],
+    );
+}
+
+#[test]
+fn component_in_owning_workspace_root() {
+    let config = Config {
+        capabilities: async_lsp::lsp_types::ClientCapabilities::default(),
+        roots: vec![
+            WorkspaceRoot::new("app".to_string(), "/app".into()),
+            WorkspaceRoot::new("courier".to_string(), "/packages/courier".into()),
+        ],
+        client_info: None,
+        settings: Default::default(),
+    };
+    check_with_config(
+        &config,
+        r#"
+//- /app/resources/views/components/alert.blade.php
+<div class="app">{{ $slot }}</div>
+//- /packages/courier/resources/views/components/alert.blade.php
+<div class="courier">{{ $slot }}</div>
+//- /packages/courier/resources/views/mail.blade.php
+<x-al$0ert/>
+"#,
+        expect![[r#"
+            /packages/courier/resources/views\components\alert.blade.php
+        "#]],
+    );
+}
+
+#[test]
+fn component_in_index_file() {
+    check(
+        r#"
+//- /resources/views/components/accordion/index.blade.php
+<div>{{ $slot }}</div>
+//- /resources/views/components/accordion/item.blade.php
+<section>{{ $slot }}</section>
+//- /resources/views/index.blade.php
+<x-accor$0dion></x-accordion>
+"#,
+        expect![[r#"
+            /resources/views\components\accordion\index.blade.php
+        "#]],
+    );
+}
+
+#[test]
+fn namespaced_component() {
+    check(
+        r#"
+//- /app/Providers/AppServiceProvider.php
+<?php
+
+namespace App\Providers;
+
+use Illuminate\Support\Facades\Blade;
+use Illuminate\Support\ServiceProvider;
+
+class AppServiceProvider extends ServiceProvider
+{
+    public function boot(): void
+    {
+        Blade::componentNamespace('Nightshade\\Views\\Components', 'nightshade');
+    }
+}
+//- /vendor/nightshade/ui/src/Views/Components/Calendar.php
+<?php
+
+namespace Nightshade\Views\Components;
+
+class Calendar extends Component
+{
+    public function __construct(
+        $month,
+    ){}
+}
+//- /resources/views/index.blade.php
+<x-nightshade::cal$0endar/>
+"#,
+        expect![[r#"
+            /vendor/nightshade/ui/src/Views/Components/Calendar.php 53..61
+        "#]],
+    );
+}
+
+#[test]
+fn aliased_component() {
+    check(
+        r#"
+//- /app/Providers/AppServiceProvider.php
+<?php
+
+namespace App\Providers;
+
+use App\Support\Alert;
+use Illuminate\Support\Facades\Blade;
+use Illuminate\Support\ServiceProvider;
+
+class AppServiceProvider extends ServiceProvider
+{
+    public function boot(): void
+    {
+        Blade::component('alert', Alert::class);
+    }
+}
+//- /app/Support/Alert.php
+<?php
+
+namespace App\Support;
+
+class Alert extends Component
+{
+    public function __construct(
+        public string $type,
+        public string $message = 'Saved',
+    ){}
+}
+//- /resources/views/index.blade.php
+<x-al$0ert/>
+"#,
+        expect![[r#"
+            /app/Support/Alert.php 37..42
+        "#]],
+    );
+}
+
+#[test]
+fn package_component() {
+    check(
+        r#"
+//- /vendor/acme/courier/resources/views/components/alert.blade.php
+<div>{{ $slot }}</div>
+//- /resources/views/index.blade.php
+<x-courier::al$0ert>Sent</x-courier::alert>
+"#,
+        expect![[r#"
+            /vendor\acme\courier\resources/views\components\alert.blade.php
+        "#]],
+    );
+}
+
+#[test]
+fn component_named_after_its_directory() {
+    check(
+        r#"
+//- /resources/views/components/accordion/accordion.blade.php
+<div>{{ $slot }}</div>
+//- /resources/views/index.blade.php
+<x-accor$0dion></x-accordion>
+"#,
+        expect![[r#"
+            /resources/views\components\accordion\accordion.blade.php
+        "#]],
+    );
+}
+
 #[test]
 fn class_component() {
     check(
@@ -68,12 +261,65 @@ Hello {{ $world }}
 <x-f$0oo/>
 "#,
         expect![[r#"
-            /app/View/Components\Foo.php
+            /app/View/Components\Foo.php 13..16
             /resources/views\components\foo.blade.php
         "#]],
     );
 }
 
+#[test]
+fn class_component_rendering_another_view() {
+    check(
+        r#"
+//- /app/View/Components/Card.php
+<?php
+
+class Card extends Component
+{
+    public function render()
+    {
+        return View::make('shared.card');
+    }
+}
+//- /resources/views/shared/card.blade.php
+<div>{{ $slot }}</div>
+//- /resources/views/index.blade.php
+<x-ca$0rd/>
+"#,
+        expect![[r#"
+            /app/View/Components\Card.php 13..17
+            /resources/views\shared\card.blade.php
+        "#]],
+    );
+}
+
+#[test]
+fn class_component_property() {
+    check(
+        r#"
+//- /app/View/Components/Alert.php
+<?php
+
+namespace App\View\Components;
+
+class Alert extends Component
+{
+    public function __construct(public string $title) {}
+
+    public function render()
+    {
+        return view('components.shared.alert');
+    }
+}
+//- /resources/views/components/shared/alert.blade.php
+<div>{{ $ti$0tle }}</div>
+"#,
+        expect![[r#"
+            /app/View/Components/Alert.php 118..123
+        "#]],
+    );
+}
+
 #[test]
 fn anon_component() {
     check(
@@ -89,7 +335,7 @@ $x
 <x-foo $0/>
 "#,
         expect![[r#"
-            /resources/views\components\foo.blade.php
+            /resources/views\components\foo.blade.php 0..24
         "#]],
     );
 }
@@ -161,3 +407,161 @@ $0
         expect_test::expect![""],
     );
 }
+
+#[test]
+fn included_view() {
+    check(
+        r#"
+//- /resources/views/partials/header.blade.php
+<header>{{ $title }}</header>
+//- /resources/views/index.blade.php
+@include('partials.hea$0der', ['title' => 'Home'])
+"#,
+        expect![[r#"
+            /resources/views\partials\header.blade.php
+        "#]],
+    );
+}
+
+#[test]
+fn view_chosen_by_ternary() {
+    check(
+        r#"
+//- /resources/views/cards/small.blade.php
+Small
+//- /resources/views/index.blade.php
+@include($compact ? 'cards.sm$0all' : 'cards.large')
+"#,
+        expect_test::expect![""],
+    );
+}
+
+#[test]
+fn include_when_view_is_second_argument() {
+    check(
+        r#"
+//- /resources/views/partials/banner.blade.php
+<div class="banner"></div>
+//- /resources/views/index.blade.php
+@includeWhen($promo, 'partials.ban$0ner')
+"#,
+        expect![[r#"
+            /resources/views\partials\banner.blade.php
+        "#]],
+    );
+}
+
+#[test]
+fn include_first_candidates() {
+    check(
+        r#"
+//- /resources/views/custom/admin.blade.php
+Custom admin
+//- /resources/views/admin.blade.php
+Admin
+//- /resources/views/index.blade.php
+@includeFirst(['custom.admin', 'adm$0in'])
+"#,
+        expect![[r#"
+            /resources/views\admin.blade.php
+        "#]],
+    );
+}
+
+#[test]
+fn each_empty_view() {
+    check(
+        r#"
+//- /resources/views/jobs/job.blade.php
+{{ $job->title }}
+//- /resources/views/jobs/none.blade.php
+No jobs
+//- /resources/views/index.blade.php
+@each('jobs.job', $jobs, 'job', 'jobs.no$0ne')
+"#,
+        expect![[r#"
+            /resources/views\jobs\none.blade.php
+        "#]],
+    );
+}
+
+#[test]
+fn extended_layout() {
+    check(
+        r#"
+//- /resources/views/layouts/app.blade.php
+@yield('content')
+//- /resources/views/index.blade.php
+@extends('layouts.a$0pp')
+"#,
+        expect![[r#"
+            /resources/views\layouts\app.blade.php
+        "#]],
+    );
+}
+
+#[test]
+fn section_to_parent_yield() {
+    check(
+        r#"
+//- /resources/views/layouts/app.blade.php
+<main>@yield('content')</main>
+//- /resources/views/index.blade.php
+@extends('layouts.app')
+
+@section('con$0tent')
+    Hello
+@endsection
+"#,
+        expect![[r#"
+            /resources/views/layouts/app.blade.php 13..22
+        "#]],
+    );
+}
+
+#[test]
+fn yield_to_child_sections() {
+    check(
+        r#"
+//- /resources/views/layouts/app.blade.php
+<main>@yield('cont$0ent')</main>
+//- /resources/views/index.blade.php
+@extends('layouts.app')
+
+@section('content')
+    Hello
+@endsection
+//- /resources/views/about.blade.php
+@extends('layouts.app')
+@section('content')
+    About
+@endsection
+"#,
+        expect![[r#"
+            /resources/views/about.blade.php 33..42
+            /resources/views/index.blade.php 34..43
+        "#]],
+    );
+}
+
+#[test]
+fn parent_to_ancestor_section() {
+    check(
+        r#"
+//- /resources/views/layouts/app.blade.php
+@section('sidebar')
+    Main sidebar
+@show
+//- /resources/views/index.blade.php
+@extends('layouts.app')
+
+@section('sidebar')
+    @par$0ent
+    Extra links
+@endsection
+"#,
+        expect![[r#"
+            /resources/views/layouts/app.blade.php 9..18
+        "#]],
+    );
+}
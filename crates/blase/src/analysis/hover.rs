@@ -2,15 +2,20 @@ use crate::db::{
     def::DocumentId,
     documentation::{Documentation, HasDocs},
 };
+use camino::Utf8Path;
 use line_index::{TextRange, TextSize};
 use macros::format_to;
 use type_sitter::{Node, UntypedNode};
 
 use crate::{
-    config::Config,
+    config::{Config, WorkspaceRoot},
     db::{
         FilePosition, ParsedDocument,
-        def::{Component, DefDatabase, Layout},
+        def::{
+            Component, ComponentAttr, DefDatabase, Layout,
+            class_component::ClassComponent,
+            views::{View, ViewReference},
+        },
     },
 };
 
@@ -49,6 +54,7 @@ fn markup(
     rel_path: String,
     source_code: String,
     doc: Option<Documentation>,
+    attrs: &[ComponentAttr],
     is_neovim: bool,
 ) -> Markup {
     let mut buf = String::new();
@@ -67,6 +73,18 @@ fn markup(
         let doc = String::from(doc);
         format_to!(buf, "\n{}", doc.trim());
     }
+    if !attrs.is_empty() {
+        buf.push('\n');
+    }
+    for attr in attrs {
+        format_to!(buf, "\n- `{}`", attr.tag_name());
+        if let Some(summary) = attr.summary() {
+            format_to!(buf, ": {summary}");
+        }
+        if let Some(docs) = &attr.docs {
+            format_to!(buf, " — {docs}");
+        }
+    }
     Markup(buf)
 }
 
@@ -80,6 +98,7 @@ pub struct HoverResult {
 enum Hoverable {
     Component(Component),
     Layout(Layout),
+    View(View, ViewReference),
 }
 
 impl Hoverable {
@@ -87,48 +106,54 @@ impl Hoverable {
         db: &dyn DefDatabase,
         node: UntypedNode,
         doc: &ParsedDocument,
-        config: &Config,
+        root: &WorkspaceRoot,
     ) -> Option<Self> {
         ast::match_node!(node,  {
             ast::blade::TagName(tag) => {
-                Component::for_tagname(db, tag, doc, config)
+                Component::for_tagname(db, tag, doc, root)
                     .map(Hoverable::Component)
-                    .or_else(|| Layout::for_tagname(db, tag, doc, config).map(Hoverable::Layout))
+                    .or_else(|| Layout::for_tagname(db, tag, doc, root).map(Hoverable::Layout))
+            },
+            _ => {
+                let reference = ViewReference::for_node(db, doc, node)?;
+                let view = reference.resolve(db, root)?;
+                Some(Hoverable::View(view, reference))
             },
-            _ => None,
         })
     }
 }
 
+/// Paths are shown relative to the workspace folder, unless they are in a
+/// directory configured outside of it
+fn display_path<'a>(path: &'a Utf8Path, root: &WorkspaceRoot) -> &'a Utf8Path {
+    path.strip_prefix(&root.folder).unwrap_or(path)
+}
+
 pub fn hover(
     db: &dyn DefDatabase,
     config: &Config,
     FilePosition { path, offset }: FilePosition,
 ) -> Option<HoverResult> {
-    let doc = &db.parsed_document(&path)?;
+    let root = config.root_for(&path);
+    let doc = &ClassComponent::document_at(db, db.parsed_document(&path)?, offset, root);
     let node = doc.get_node_at(offset)?;
-    let hoverable = Hoverable::from_node(db, node, doc, config)?;
+    let hoverable = Hoverable::from_node(db, node, doc, root)?;
 
     let is_neovim = config.client_is_neovim();
 
     match hoverable {
         Hoverable::Component(component) => {
-            let rel_path = component
-                .id
-                .path(db)
-                .strip_prefix(config.workspace_folder())
-                .expect("Component is not in the workspace folder. This is a bug");
+            let rel_path = display_path(component.id.path(db), root);
             let mut label = format!("<{}", component.name(db).tag_name());
-            if let Some(attrs) = component.attrs(db) {
-                for attr in attrs.as_ref() {
-                    label.push(' ');
-                    label.push_str(attr.name.as_str());
-                    format_to!(
-                        label,
-                        "=\"{}\"",
-                        attr.default_value.clone().unwrap_or_default()
-                    );
-                }
+            let attrs = component.attrs(db).unwrap_or_default();
+            for attr in attrs.as_ref() {
+                label.push(' ');
+                label.push_str(&attr.tag_name());
+                format_to!(
+                    label,
+                    "=\"{}\"",
+                    attr.default_value.clone().unwrap_or_default()
+                );
             }
             label.push('>');
             let range = TextRange::new(
@@ -136,15 +161,11 @@ pub fn hover(
                 TextSize::new(node.byte_range().end as u32),
             );
             let docs = component.docs(db);
-            let markup = markup(rel_path.to_string(), label, docs, is_neovim);
+            let markup = markup(rel_path.to_string(), label, docs, &attrs, is_neovim);
             Some(HoverResult { markup, range })
         }
         Hoverable::Layout(layout) => {
-            let rel_path = layout
-                .id
-                .path(db)
-                .strip_prefix(config.workspace_folder())
-                .expect("Layout is not in the workspace folder. This is a bug");
+            let rel_path = display_path(layout.id.path(db), root);
             let tag_name = &layout.name(db).tag_name();
             let mut label = format!("<{}>\n", tag_name);
             label.push_str("  {{ $slot }}\n");
@@ -153,8 +174,22 @@ pub fn hover(
                 TextSize::new(node.byte_range().start as u32),
                 TextSize::new(node.byte_range().end as u32),
             );
-            let markup = markup(rel_path.to_string(), label, None, is_neovim);
+            let markup = markup(rel_path.to_string(), label, None, &[], is_neovim);
             Some(HoverResult { markup, range })
         }
+        Hoverable::View(view, reference) => {
+            let rel_path = display_path(view.id.path(db), root);
+            let label = format!(
+                "{}('{}')",
+                reference.directive.label(),
+                view.name(db).as_str()
+            );
+            let docs = view.docs(db);
+            let markup = markup(rel_path.to_string(), label, docs, &[], is_neovim);
+            Some(HoverResult {
+                markup,
+                range: reference.range,
+            })
+        }
     }
 }
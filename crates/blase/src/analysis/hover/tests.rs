@@ -6,8 +6,12 @@ use crate::{analysis::fixture, config::Config};
 
 const TEST_CONFIG: LazyLock<Config> = LazyLock::new(|| Config {
     capabilities: async_lsp::lsp_types::ClientCapabilities::default(),
-    workspace_folder: camino::Utf8PathBuf::from_path_buf(std::path::PathBuf::from("/")).unwrap(),
+    roots: vec![crate::config::WorkspaceRoot::new(
+        "test".to_string(),
+        camino::Utf8PathBuf::from_path_buf(std::path::PathBuf::from("/")).unwrap(),
+    )],
     client_info: None,
+    settings: Default::default(),
 });
 
 fn check_no_hover(blade_fixture: &str) {
@@ -114,6 +118,170 @@ Hello {{ $world }}
             ```blade
             <x-foo world="">
             ```
+
+            - `world`: required
+        "#]],
+    );
+}
+
+#[test]
+fn test_hover_on_typed_class_component() {
+    check(
+        r#"
+//- /app/View/Components/Alert.php
+<?php
+
+namespace App\View\Components;
+
+class Alert extends Component
+{
+    /**
+     * @param 'info'|'error' $type The kind of alert
+     */
+    public function __construct(
+        public string $type,
+        public ?string $title,
+        public int $timeout = 5,
+    ) {}
+}
+//- /resources/views/index.blade.php
+<x-al$0ert/>
+"#,
+        expect![[r#"
+            *x-alert*
+            *Project Path*: app\View\Components\Alert.php
+            ___
+            ```blade
+            <x-alert type="" title="" timeout="5">
+            ```
+
+            - `type`: `'info'|'error'`, required — The kind of alert
+            - `title`: `?string`
+            - `timeout`: `int`, defaults to `5`
+        "#]],
+    );
+}
+
+#[test]
+fn test_hover_on_class_component_documented_in_its_view() {
+    check(
+        r#"
+//- /app/View/Components/Card.php
+<?php
+
+class Card extends Component
+{
+    public function __construct(public string $title) {}
+
+    public function render()
+    {
+        return view('shared.card');
+    }
+}
+//- /resources/views/shared/card.blade.php
+{{--
+--  A card with a title
+--}}
+<h1>{{ $title }}</h1>
+//- /resources/views/index.blade.php
+<x-ca$0rd/>
+"#,
+        expect![[r#"
+            *x-card*
+            *Project Path*: app\View\Components\Card.php
+            ___
+            ```blade
+            <x-card title="">
+            ```
+            A card with a title
+
+            - `title`: `string`, required
+        "#]],
+    );
+}
+
+#[test]
+fn test_hover_in_inline_template() {
+    check(
+        r#"
+//- /resources/views/components/foo.blade.php
+{{--
+--  This is a comment
+--}}
+@props(['x', 'y' => []])
+$x
+//- /app/View/Components/Alert.php
+<?php
+
+class Alert extends Component
+{
+    public function render()
+    {
+        return <<<'blade'
+            <div>
+                <x-f$0oo x="1"/>
+            </div>
+        blade;
+    }
+}
+"#,
+        expect![[r#"
+            *x-foo*
+            *Project Path*: resources\views\components\foo.blade.php
+            ___
+            ```blade
+            <x-foo x="" y="[]">
+            ```
+            This is a comment
+
+            - `x`: required
+            - `y`: defaults to `[]`
+        "#]],
+    );
+}
+
+#[test]
+fn test_hover_on_namespaced_component() {
+    check(
+        r#"
+//- /app/Providers/AppServiceProvider.php
+<?php
+
+namespace App\Providers;
+
+use Illuminate\Support\Facades\Blade;
+use Illuminate\Support\ServiceProvider;
+
+class AppServiceProvider extends ServiceProvider
+{
+    public function boot(): void
+    {
+        Blade::componentNamespace('Nightshade\\Views\\Components', 'nightshade');
+    }
+}
+//- /vendor/nightshade/ui/src/Views/Components/Calendar.php
+<?php
+
+namespace Nightshade\Views\Components;
+
+class Calendar extends Component
+{
+    public function __construct(
+        $month,
+    ){}
+}
+//- /resources/views/index.blade.php
+<x-nightshade::cal$0endar/>
+"#,
+        expect![[r#"
+            *x-nightshade::calendar*
+            *Project Path*: vendor\nightshade\ui\src\Views\Components\Calendar.php
+            ___
+            ```blade
+            <x-nightshade::calendar month="">
+            ```
+
+            - `month`: required
         "#]],
     );
 }
@@ -139,6 +307,9 @@ $x
             ```blade
             <x-foo x="" y="[]">
             ```
+
+            - `x`: required
+            - `y`: defaults to `[]`
         "#]],
     );
 }
@@ -165,6 +336,9 @@ $x
             <x-foo x="" y="[]">
             ```
             This is a comment
+
+            - `x`: required
+            - `y`: defaults to `[]`
         "#]],
     );
 }
@@ -213,6 +387,9 @@ $x
             ```blade
             <x-foo x="" y="[]">
             ```
+
+            - `x`: required
+            - `y`: defaults to `[]`
         "#]],
     );
 }
@@ -260,3 +437,38 @@ test
         "#]],
     );
 }
+
+#[test]
+fn test_hover_on_included_view_with_documentation() {
+    check(
+        r#"
+//- /resources/views/partials/header.blade.php
+{{--
+--  Site-wide page header
+--}}
+<header>{{ $title }}</header>
+
+//- /resources/views/index.blade.php
+@include('partials.he$0ader', ['title' => 'Home'])
+"#,
+        expect![[r#"
+            *'partials.header'*
+            *Project Path*: resources\views\partials\header.blade.php
+            ___
+            ```blade
+            @include('partials.header')
+            ```
+            Site-wide page header
+        "#]],
+    );
+}
+
+#[test]
+fn no_hover_on_missing_view() {
+    check_no_hover(
+        r#"
+//- /resources/views/index.blade.php
+@include('partials.he$0ader')
+"#,
+    );
+}
@@ -3,11 +3,12 @@ use camino::Utf8Path;
 use crate::{
     analysis::{
         Analysis, Cancellable, completions, diagnostics, goto_definition, hover, references,
-        signature_help, workspace_symbols,
+        rename, signature_help, workspace_symbols,
     },
     config::Config,
-    db::{FilePosition, FileRange},
+    db::{FilePosition, FileRange, source_change::SourceChange},
 };
+use line_index::TextRange;
 
 impl Analysis {
     #[tracing::instrument(skip(self, config))]
@@ -71,4 +72,23 @@ impl Analysis {
     ) -> Cancellable<Option<Vec<workspace_symbols::SymbolInformation>>> {
         self.with_db(|db| workspace_symbols::workspace_symbols(db, query, config))
     }
+
+    #[tracing::instrument(skip(self, config))]
+    pub fn prepare_rename(
+        &self,
+        config: &Config,
+        position: FilePosition,
+    ) -> Cancellable<rename::RenameResult<TextRange>> {
+        self.with_db(|db| rename::prepare_rename(db, config, position))
+    }
+
+    #[tracing::instrument(skip(self, config))]
+    pub fn rename(
+        &self,
+        config: &Config,
+        position: FilePosition,
+        new_name: &str,
+    ) -> Cancellable<rename::RenameResult<SourceChange>> {
+        self.with_db(|db| rename::rename(db, config, position, new_name))
+    }
 }
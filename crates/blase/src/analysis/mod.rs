@@ -26,6 +26,7 @@ pub use diagnostics::Diagnostic;
 mod goto_definition;
 pub mod hover;
 mod lsp;
+pub mod rename;
 pub mod signature_help;
 
 impl AnalysisHost {
@@ -33,6 +34,10 @@ impl AnalysisHost {
         self.db.set_source_file(path, contents);
     }
 
+    pub fn remove_source_file(&mut self, path: &Utf8Path) -> bool {
+        self.db.remove_source_file(path).is_some()
+    }
+
     pub fn analysis(&self) -> Analysis {
         Analysis {
             db: self.db.clone(),
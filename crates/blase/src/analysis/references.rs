@@ -6,19 +6,24 @@ use tree_sitter::{Query, QueryCursor, StreamingIterator};
 use type_sitter::{Node, UntypedNode};
 
 use crate::{
-    config::Config,
+    config::{Config, WorkspaceRoot},
     db::{
-        DocumentDatabase, FilePosition, ParsedDocument, RootDatabase,
-        def::{ComponentName, LayoutName, queries},
+        DocumentDatabase, FilePosition, FileRange, ParsedDocument, RootDatabase,
+        def::{
+            Component, ComponentAttr, ComponentName, Document, DocumentId, LayoutName,
+            attributes::{self, AttrDeclaration, ComponentUsage},
+            class_component::{self, ClassComponent},
+            queries,
+            stacks::Stacks,
+        },
     },
     resolve_path,
-    util::FileType,
 };
 
 pub struct ReferenceSearchResult {
-    /// If Some, it stores the file paths of the document that defines the component/layout
-    /// If None, it is a builtin directive
-    pub defined_files: Option<Box<[Utf8PathBuf]>>,
+    /// If Some, it stores where the component/layout or attribute is declared
+    /// If None, nothing defines it, e.g. a builtin directive or a stack
+    pub definitions: Option<Box<[FileRange]>>,
 
     /// All references found, grouped by file
     pub references: HashMap<Utf8PathBuf, Vec<TextRange>>,
@@ -30,35 +35,153 @@ pub fn references(
     position: FilePosition,
 ) -> Option<Vec<ReferenceSearchResult>> {
     let path = &position.path;
+    let root = config.root_for(path);
     let offset = position.offset;
     let document = db.parsed_document(path)?;
+    let document = ClassComponent::document_at(db, document, offset, root);
     let current_node = document.get_node_at(offset)?;
     ast::match_node!(current_node, {
-        ast::blade::TagName(tag_name) => handle_component_or_layout_references(db, config, tag_name, &document),
-        _ => None,
+        ast::blade::TagName(tag_name) => handle_component_or_layout_references(db, root, tag_name, &document),
+        _ => handle_attribute_references(db, root, &document, current_node, offset)
+            .or_else(|| handle_stack_references(db, &document, offset)),
     })
 }
 
+/// Finds every usage that passes the attribute under the cursor and every
+/// place the component reads it. The cursor is either on the declaration,
+/// see [`AttrDeclaration`], or on an attribute passed to a component.
+pub fn handle_attribute_references(
+    db: &RootDatabase,
+    root: &WorkspaceRoot,
+    document: &ParsedDocument,
+    node: UntypedNode<'_>,
+    offset: TextSize,
+) -> Option<Vec<ReferenceSearchResult>> {
+    let (component, name) = match AttrDeclaration::at(db, document, offset) {
+        Some(declaration) => (
+            Component::for_document(db, document, root)?,
+            declaration.name.to_owned(),
+        ),
+        None => {
+            let attr = node
+                .ancestors()
+                .find_map(|node| node.downcast::<ast::blade::Attribute>().ok())?;
+            let (name, range) = ComponentAttr::passed_name(db, document, attr)?;
+            if !range.contains_inclusive(offset) {
+                return None;
+            }
+            let component = Component::for_attr(db, attr, document, root)?;
+            // Look for the attribute as declared, which `due-date` passes as `$dueDate`
+            let declared = component
+                .attrs(db)
+                .and_then(|attrs| {
+                    let attr = attrs.iter().find(|attr| attr.is_passed_as(name))?;
+                    Some(attr.name.to_string())
+                })
+                .unwrap_or_else(|| name.to_owned());
+            (component, declared)
+        }
+    };
+
+    let mut references = HashMap::<_, Vec<_>>::new();
+    for (path, usages) in ComponentUsage::all(db, &component.name(db)) {
+        let ranges = usages
+            .iter()
+            .flat_map(|usage| &usage.attrs)
+            .filter(|attr| attributes::same_attribute(&name, attr.name.as_str()))
+            .map(|attr| attr.range);
+        references.entry(path).or_default().extend(ranges);
+    }
+
+    let defining = component.id().document(db);
+    let declaration = AttrDeclaration::named(db, &defining, &name);
+    for read in declaration
+        .iter()
+        .flat_map(|declaration| declaration.reads(db, &defining, component))
+    {
+        references.entry(read.path).or_default().push(read.range);
+    }
+    references.retain(|_, ranges| !ranges.is_empty());
+
+    let definitions = declaration.map(|declaration| {
+        [FileRange {
+            path: defining.source.path(db).to_owned(),
+            range: declaration.range,
+        }]
+        .into()
+    });
+    Some(vec![ReferenceSearchResult {
+        definitions,
+        references,
+    }])
+}
+
+/// Finds every `@push` and `@prepend` to the stack under the cursor
+pub fn handle_stack_references(
+    db: &RootDatabase,
+    document: &ParsedDocument,
+    offset: TextSize,
+) -> Option<Vec<ReferenceSearchResult>> {
+    let stacks = Stacks::for_document(db, document);
+    let name = stacks
+        .renders
+        .iter()
+        .chain(stacks.pushes.iter())
+        .find(|site| site.range.contains_inclusive(offset))
+        .map(|site| site.name.clone())?;
+    let references = db
+        .all_documents()
+        .iter()
+        .filter_map(|doc| {
+            let ranges = Stacks::for_document(db, doc)
+                .pushes
+                .iter()
+                .filter(|site| site.name == name)
+                .map(|site| site.range)
+                .collect::<Vec<_>>();
+            if ranges.is_empty() {
+                None
+            } else {
+                Some((doc.source.path(db).to_owned(), ranges))
+            }
+        })
+        .collect::<HashMap<_, _>>();
+    Some(vec![ReferenceSearchResult {
+        definitions: None,
+        references,
+    }])
+}
+
+/// Finds the tag names of every element that uses the component or layout
+/// under the cursor, including those of end tags
 pub fn handle_component_or_layout_references(
     db: &RootDatabase,
-    config: &Config,
+    root: &WorkspaceRoot,
     tag_name: ast::blade::TagName<'_>,
     document: &ParsedDocument,
 ) -> Option<Vec<ReferenceSearchResult>> {
     let name = document.text_for_node(db, tag_name)?;
-    let (name, (class_path, resources_path)) = match ComponentName::new(name) {
-        Some(name) => (
-            &name.tag_name(),
-            resolve_path::component_paths(&name, config),
-        ),
+    let (name, paths) = match ComponentName::new(name) {
+        Some(name) => {
+            let (class_path, _) = resolve_path::component_paths(db, &name, root);
+            let template = resolve_path::component_template(db, &name, root);
+            (
+                &name.tag_name(),
+                std::iter::once(class_path).chain(template).collect(),
+            )
+        }
         None => {
             let name = LayoutName::new(name)?;
-            (&name.tag_name(), resolve_path::layout_paths(&name, config))
+            let (class_path, resources_path) = resolve_path::layout_paths(db, &name, root);
+            (&name.tag_name(), vec![class_path, resources_path])
         }
     };
-    let doc_paths = [class_path, resources_path]
+    let definitions = paths
         .into_iter()
-        .filter(|path| db.parsed_document(path).is_some())
+        .filter_map(|path| {
+            let range = attributes::definition_range(&db.parsed_document(&path)?);
+            Some(FileRange { path, range })
+        })
         .collect::<Box<_>>();
     let tag_name = tag_name.raw();
     let query = Query::new(
@@ -66,45 +189,41 @@ pub fn handle_component_or_layout_references(
         &queries::component_and_layout_named(name),
     )
     .unwrap();
+    // Inline templates are searched too
     let references = db
         .all_documents()
-        .into_iter()
-        .filter(|doc| doc.filetype == FileType::Blade)
-        .flat_map(|doc| {
+        .iter()
+        .filter_map(|doc| class_component::blade_document(db, doc.source))
+        .filter_map(|doc| {
             let node = doc.root_node();
             let contents = doc.contents(db);
             let mut cursor = QueryCursor::new();
             let mut matches = cursor.matches(&query, *node.raw(), contents.as_bytes());
 
-            let mut captures = Vec::new();
-
+            let mut ranges = Vec::new();
             while let Some(m) = matches.next() {
-                captures.push(m.captures.to_owned());
-            }
-
-            captures
-                .into_iter()
-                .map(|captures| (captures, doc.source.path(db)))
-        })
-        .map(|(captures, path)| {
-            let ranges = captures
-                .iter()
-                .filter_map(|capture| {
-                    let node = UntypedNode::new(capture.node)
+                let elements = m.captures.iter().filter_map(|capture| {
+                    UntypedNode::new(capture.node)
                         .downcast::<ast::blade::Element>()
-                        .ok()?;
-                    let range = TextRange::new(
-                        TextSize::new(node.byte_range().start as u32),
-                        TextSize::new(node.byte_range().end as u32),
-                    );
-                    Some(range)
-                })
-                .collect::<Vec<_>>();
-            (path.to_owned(), ranges)
+                        .ok()
+                });
+                // The query also matches tags that merely start with the name
+                let tag_names = elements
+                    .flat_map(|element| [element.tag_name(), element.end_tag_name()])
+                    .flatten()
+                    .filter(|tag_name| doc.text_for_node(db, *tag_name) == Some(name.as_str()))
+                    .map(attributes::node_range);
+                ranges.extend(tag_names);
+            }
+            if ranges.is_empty() {
+                None
+            } else {
+                Some((doc.source.path(db).to_owned(), ranges))
+            }
         })
         .collect::<HashMap<_, _>>();
     Some(vec![ReferenceSearchResult {
-        defined_files: Some(doc_paths),
+        definitions: Some(definitions),
         references,
     }])
 }
@@ -3,12 +3,16 @@ use std::sync::LazyLock;
 use expect_test::{Expect, expect};
 use itertools::Itertools;
 
-use crate::{analysis::fixture, config::Config};
+use crate::{analysis::fixture, config::Config, db::FileRange};
 
 const TEST_CONFIG: LazyLock<Config> = LazyLock::new(|| Config {
     capabilities: async_lsp::lsp_types::ClientCapabilities::default(),
-    workspace_folder: camino::Utf8PathBuf::from_path_buf(std::path::PathBuf::from("/")).unwrap(),
+    roots: vec![crate::config::WorkspaceRoot::new(
+        "test".to_string(),
+        camino::Utf8PathBuf::from_path_buf(std::path::PathBuf::from("/")).unwrap(),
+    )],
     client_info: None,
+    settings: Default::default(),
 });
 
 fn check_no_usages(fixture: &str) {
@@ -25,9 +29,9 @@ fn check(fixture: &str, expect: Expect) {
         .unwrap();
 
     refs.sort_by_key(|refs| {
-        refs.defined_files
+        refs.definitions
             .clone()
-            .and_then(|path| path.first().cloned())
+            .and_then(|definitions| definitions.first().map(|def| def.path.clone()))
     });
 
     let mut actual = String::new();
@@ -39,11 +43,11 @@ fn check(fixture: &str, expect: Expect) {
             continue;
         }
 
-        if let Some(files) = refs.defined_files {
-            for file in files {
-                actual += file.as_str();
+        if let Some(definitions) = refs.definitions {
+            for FileRange { path, range } in definitions.iter() {
+                macros::format_to!(actual, "{} {:?}\n", path, range);
             }
-            actual += "\n\n";
+            actual += "\n";
         }
 
         let refs = refs.references;
@@ -91,8 +95,9 @@ It's so nice to see you
 Welcome to Laravel!
 "#,
         expect![[r#"
-            "/resources/views/one.blade.php" 39..56 <x-hello-layout/>
-            "/resources/views/two.blade.php" 0..17 <x-hello-layout/>
+            "/resources/views/one.blade.php" 21..35 x-hello-layout
+            "/resources/views/one.blade.php" 40..54 x-hello-layout
+            "/resources/views/two.blade.php" 1..15 x-hello-layout
         "#]],
     )
 }
@@ -116,13 +121,110 @@ Welcome to Laravel!
         //FIXME: Sometimes, the test outputs change every run. However, the only
         //thing changing is the ordering of the references list (sometimes ascending or descending).
         //
-        // I've sorted the references list by the defined_files field, but I'll leave the comment above
+        // I've sorted the references list by the definitions field, but I'll leave the comment above
         // just in case this comes up again.
         expect![[r#"
-                /resources/views\components\hello.blade.php
+                /resources/views\components\hello.blade.php 0..0
 
-                "/resources/views/one.blade.php" 32..42 <x-hello/>
-                "/resources/views/two.blade.php" 0..10 <x-hello/>
+                "/resources/views/one.blade.php" 21..28 x-hello
+                "/resources/views/one.blade.php" 33..40 x-hello
+                "/resources/views/two.blade.php" 1..8 x-hello
             "#]],
     )
 }
+
+#[test]
+fn find_stack_pushers() {
+    check(
+        r#"
+//- /resources/views/layouts/app.blade.php
+@stack('scr$0ipts')
+//- /resources/views/one.blade.php
+@push('scripts')
+    <script src="one.js"></script>
+@endpush
+//- /resources/views/two.blade.php
+@prepend('scripts')
+    <script src="two.js"></script>
+@endprepend
+@push('styles')
+@endpush
+"#,
+        expect![[r#"
+            "/resources/views/one.blade.php" 6..15 'scripts'
+            "/resources/views/two.blade.php" 9..18 'scripts'
+        "#]],
+    )
+}
+
+#[test]
+fn find_prop_references() {
+    check(
+        r#"
+//- /resources/views/components/alert.blade.php
+@props(['ti$0tle'])
+<div>{{ $title }}</div>
+//- /resources/views/index.blade.php
+<x-alert title="Hi"/>
+<x-alert :title="$heading"></x-alert>
+<x-alert :$title/>
+"#,
+        expect![[r#"
+            /resources/views/components/alert.blade.php 9..14
+
+            "/resources/views/components/alert.blade.php" 26..32 $title
+            "/resources/views/index.blade.php" 9..14 title
+            "/resources/views/index.blade.php" 32..37 title
+            "/resources/views/index.blade.php" 71..76 title
+        "#]],
+    )
+}
+
+#[test]
+fn find_attribute_references_from_usage() {
+    check(
+        r#"
+//- /app/View/Components/Alert.php
+<?php
+
+namespace App\View\Components;
+
+class Alert extends Component
+{
+    public function __construct(
+        public string $title,
+    ) {}
+}
+//- /resources/views/components/alert.blade.php
+<div>{{ $title }}</div>
+//- /resources/views/index.blade.php
+<x-alert ti$0tle="Hi"/>
+"#,
+        expect![[r#"
+            /app/View/Components/Alert.php 127..132
+
+            "/resources/views/components/alert.blade.php" 8..14 $title
+            "/resources/views/index.blade.php" 9..14 title
+        "#]],
+    )
+}
+
+#[test]
+fn find_component_usage_with_end_tags() {
+    check(
+        r#"
+//- /resources/views/components/alert.blade.php
+@props(['type'])
+//- /resources/views/index.blade.php
+<x-al$0ert type="info">
+    <x-alert-icon/>
+</x-alert>
+"#,
+        expect![[r#"
+            /resources/views\components\alert.blade.php 0..16
+
+            "/resources/views/index.blade.php" 1..8 x-alert
+            "/resources/views/index.blade.php" 44..51 x-alert
+        "#]],
+    )
+}
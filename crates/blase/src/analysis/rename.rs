@@ -0,0 +1,584 @@
+//! Renaming a component or a layout rewrites every tag that uses it, moves
+//! its template and class to where the new name is looked up and renames
+//! the class to match.
+
+use ast::NodeExt;
+use camino::{Utf8Path, Utf8PathBuf};
+use itertools::Itertools;
+use line_index::{TextRange, TextSize};
+use type_sitter::UntypedNode;
+
+use crate::{
+    analysis::references,
+    config::{Config, WorkspaceRoot},
+    db::{
+        DocumentDatabase, FilePosition, ParsedDocument, RootDatabase,
+        def::{
+            Component, ComponentName, LayoutName,
+            attributes::{
+                self, AttrDeclaration, AttrReadKind, ComponentUsage, PassedAttrKind, node_range,
+            },
+            class_component::ClassComponent,
+            php::{PhpFile, field},
+            providers::{self, Registration},
+        },
+        source_change::{FileSystemEdit, SourceChange},
+        text_edit::{TextEdit, TextEditBuilder},
+    },
+    resolve_path,
+    util::FileType,
+};
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RenameError(pub String);
+
+impl std::fmt::Display for RenameError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        self.0.fmt(f)
+    }
+}
+
+pub type RenameResult<T> = Result<T, RenameError>;
+
+macro_rules! bail {
+    ($($arg:tt)*) => {
+        return Err(RenameError(format!($($arg)*)))
+    };
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Target {
+    Component(ComponentName),
+    Layout(LayoutName),
+}
+
+/// The range of the name under the cursor, without the `x-` prefix of a
+/// tag or the quotes and `$` of an attribute
+pub fn prepare_rename(
+    db: &RootDatabase,
+    config: &Config,
+    position: FilePosition,
+) -> RenameResult<TextRange> {
+    let Some(document) = db.parsed_document(&position.path) else {
+        bail!("No document at {}", position.path);
+    };
+    let root = config.root_for(&position.path);
+    let document = ClassComponent::document_at(db, document, position.offset, root);
+    let Some(tag_name) = tag_name_at(&document, position.offset) else {
+        let prop = PropDefinition::at(db, root, &document, position.offset)?;
+        return Ok(prop.declaration.range);
+    };
+    let target = Target::from_tag_name(db, &document, tag_name)?;
+    // Fail early instead of after the user has typed the new name
+    target.defining_files(db, root)?;
+    target.check_file_moves(config)?;
+    target.check_class_references(db, root)?;
+    let range = node_range(tag_name);
+    Ok(TextRange::new(
+        range.start() + TextSize::of("x-"),
+        range.end(),
+    ))
+}
+
+pub fn rename(
+    db: &RootDatabase,
+    config: &Config,
+    position: FilePosition,
+    new_name: &str,
+) -> RenameResult<SourceChange> {
+    let Some(document) = db.parsed_document(&position.path) else {
+        bail!("No document at {}", position.path);
+    };
+    let root = config.root_for(&position.path);
+    let document = ClassComponent::document_at(db, document, position.offset, root);
+    let Some(tag_name) = tag_name_at(&document, position.offset) else {
+        let prop = PropDefinition::at(db, root, &document, position.offset)?;
+        return prop.rename(db, &document, new_name);
+    };
+    let target = Target::from_tag_name(db, &document, tag_name)?;
+    let new_target = target.renamed(new_name)?;
+    let mut change = SourceChange::default();
+    if new_target == target {
+        return Ok(change);
+    }
+
+    target.check_file_moves(config)?;
+    target.check_class_references(db, root)?;
+    let moves = target.file_moves(db, root, &new_target)?;
+    if let Some(dst) = moves
+        .iter()
+        .map(|(_, dst)| dst)
+        .find(|dst| db.source_file(dst).is_some())
+    {
+        bail!(
+            "Cannot rename to `{}`, {dst} already exists",
+            new_target.tag_name()
+        );
+    }
+
+    let new_tag = new_target.tag_name();
+    let refs = references::handle_component_or_layout_references(db, root, tag_name, &document)
+        .unwrap_or_default();
+    for (path, ranges) in refs.into_iter().flat_map(|refs| refs.references) {
+        let mut builder = TextEdit::builder();
+        for range in ranges.into_iter().unique() {
+            builder.replace(range, new_tag.clone());
+        }
+        change.insert_source_edits(path, builder);
+    }
+
+    let views = moves
+        .iter()
+        .find(|(src, _)| FileType::from_path(src) == Some(FileType::Blade))
+        .and_then(|(src, dst)| view_name(root, src).zip(view_name(root, dst)));
+    for (src, dst) in moves {
+        if FileType::from_path(&src) == Some(FileType::PHP) {
+            let edit = target.class_edit(db, &src, &new_target, views.clone());
+            change.insert_source_edits(src.clone(), edit);
+        }
+        change.push_file_system_edit(FileSystemEdit::MoveFile { src, dst });
+    }
+    Ok(change)
+}
+
+fn tag_name_at(document: &ParsedDocument, offset: TextSize) -> Option<ast::blade::TagName<'_>> {
+    document
+        .get_node_at(offset)
+        .and_then(|node| node.downcast::<ast::blade::TagName>().ok())
+}
+
+/// An attribute along with the component declaring it
+struct PropDefinition<'doc> {
+    component: Component,
+    declaration: AttrDeclaration<'doc>,
+}
+
+impl<'doc> PropDefinition<'doc> {
+    fn at(
+        db: &'doc RootDatabase,
+        root: &WorkspaceRoot,
+        document: &'doc ParsedDocument,
+        offset: TextSize,
+    ) -> RenameResult<PropDefinition<'doc>> {
+        let Some(declaration) = AttrDeclaration::at(db, document, offset) else {
+            bail!("Only components, layouts and their attributes can be renamed");
+        };
+        let Some(component) = Component::for_document(db, document, root) else {
+            bail!("`{}` is not declared by a component", declaration.name);
+        };
+        Ok(PropDefinition {
+            component,
+            declaration,
+        })
+    }
+
+    /// Renames the declaration, the attributes passed on every usage of the
+    /// component and the places that read it
+    fn rename(
+        &self,
+        db: &RootDatabase,
+        document: &ParsedDocument,
+        new_name: &str,
+    ) -> RenameResult<SourceChange> {
+        let new_name = new_name.trim();
+        let new_name = new_name.strip_prefix('$').unwrap_or(new_name);
+        let is_identifier = new_name
+            .chars()
+            .next()
+            .is_some_and(|c| c.is_ascii_alphabetic() || c == '_')
+            && new_name
+                .chars()
+                .all(|c| c.is_ascii_alphanumeric() || c == '_');
+        if !is_identifier {
+            bail!("`{new_name}` is not a valid attribute name");
+        }
+        let name = self.declaration.name;
+        let mut change = SourceChange::default();
+        if new_name == name {
+            return Ok(change);
+        }
+        let old_variable = format!("${name}");
+        let new_variable = format!("${new_name}");
+
+        let path = document.source.path(db).to_owned();
+        change.insert_source_edit(
+            path,
+            TextEdit::replace(self.declaration.range, new_name.to_owned()),
+        );
+
+        let template_path = self
+            .declaration
+            .template(db, self.component)
+            .map(|template| template.source.path(db).to_owned());
+        // `:$title` passed from the template still reads the renamed variable
+        let mut short_attributes = Vec::new();
+        for (path, usages) in ComponentUsage::all(db, &self.component.name(db)) {
+            let in_template = template_path.as_ref() == Some(&path);
+            let mut builder = TextEdit::builder();
+            let attrs = usages
+                .iter()
+                .flat_map(|usage| &usage.attrs)
+                .filter(|attr| attributes::same_attribute(name, attr.name.as_str()));
+            for attr in attrs {
+                match attr.kind {
+                    PassedAttrKind::Short if in_template => {
+                        short_attributes.push(attr.attr_range);
+                        builder.replace(attr.attr_range, format!(":{new_variable}"));
+                    }
+                    PassedAttrKind::Short => {
+                        let passed = format!(":{new_name}=\"{old_variable}\"");
+                        builder.replace(attr.attr_range, passed);
+                    }
+                    // Keep the kebab-case spelling of `due-date="..."`
+                    PassedAttrKind::Html | PassedAttrKind::Expression
+                        if attr.name.as_str() != name =>
+                    {
+                        builder.replace(attr.range, attributes::tag_attribute(new_name))
+                    }
+                    PassedAttrKind::Html | PassedAttrKind::Expression => {
+                        builder.replace(attr.range, new_name.to_owned())
+                    }
+                }
+            }
+            change.insert_source_edits(path, builder);
+        }
+
+        for read in self.declaration.reads(db, document, self.component) {
+            let edit = match read.kind {
+                AttrReadKind::Variable => TextEdit::replace(read.range, new_variable.clone()),
+                AttrReadKind::Property => TextEdit::replace(read.range, new_name.to_owned()),
+                AttrReadKind::ShortAttribute { attr_range }
+                    if short_attributes.contains(&attr_range) =>
+                {
+                    continue;
+                }
+                // `:$title` passes a `title` attribute to another component
+                AttrReadKind::ShortAttribute { attr_range } => {
+                    TextEdit::replace(attr_range, format!(":{name}=\"{new_variable}\""))
+                }
+            };
+            change.insert_source_edit(read.path, edit);
+        }
+        Ok(change)
+    }
+}
+
+impl Target {
+    fn from_tag_name(
+        db: &RootDatabase,
+        document: &ParsedDocument,
+        tag_name: ast::blade::TagName<'_>,
+    ) -> RenameResult<Target> {
+        let Some(name) = document.text_for_node(db, tag_name) else {
+            bail!("Only components and layouts can be renamed");
+        };
+        if let Some(name) = ComponentName::new(name) {
+            if name.namespace().is_some() {
+                bail!(
+                    "`{}` belongs to a package and cannot be renamed",
+                    name.tag_name()
+                );
+            }
+            return Ok(Target::Component(name));
+        }
+        match LayoutName::new(name) {
+            Some(name) => Ok(Target::Layout(name)),
+            None => bail!("Only components and layouts can be renamed"),
+        }
+    }
+
+    /// `new_name` may be given with or without its `x-` prefix, but a
+    /// component stays a component and a layout stays a layout
+    fn renamed(&self, new_name: &str) -> RenameResult<Target> {
+        let new_name = new_name.trim();
+        let new_name = new_name.strip_prefix("x-").unwrap_or(new_name);
+        let is_valid = !new_name.is_empty()
+            && new_name
+                .split(['.', '-'])
+                .all(|segment| !segment.is_empty())
+            && new_name
+                .chars()
+                .all(|c| c.is_ascii_alphanumeric() || c == '.' || c == '-');
+        let tag_name = format!("x-{new_name}");
+        let renamed = match self {
+            _ if !is_valid => None,
+            Target::Component(_) => ComponentName::new(&tag_name).map(Target::Component),
+            Target::Layout(_) => LayoutName::new(&tag_name).map(Target::Layout),
+        };
+        match (renamed, self) {
+            (Some(renamed), _) => Ok(renamed),
+            (None, Target::Component(_)) => bail!("`{tag_name}` is not a valid component name"),
+            (None, Target::Layout(_)) => {
+                bail!("`{tag_name}` is not a valid layout name, layouts end with `-layout`")
+            }
+        }
+    }
+
+    fn tag_name(&self) -> String {
+        match self {
+            Target::Component(name) => name.tag_name(),
+            Target::Layout(name) => name.tag_name(),
+        }
+    }
+
+    /// The class and the template of the target, whichever exist
+    fn defining_files(
+        &self,
+        db: &RootDatabase,
+        root: &WorkspaceRoot,
+    ) -> RenameResult<Vec<Utf8PathBuf>> {
+        let (class_path, resources_path) = match self {
+            Target::Component(name) => resolve_path::component_paths(db, name, root),
+            Target::Layout(name) => resolve_path::layout_paths(db, name, root),
+        };
+        let files = [class_path, resources_path]
+            .into_iter()
+            .filter(|path| db.source_file(path).is_some())
+            .collect::<Vec<_>>();
+        if files.is_empty() {
+            bail!("Cannot find the definition of `{}`", self.tag_name());
+        }
+        Ok(files)
+    }
+
+    /// The defining files move along with the name, so a client that can't
+    /// rename files would be left with references to files that don't exist
+    fn check_file_moves(&self, config: &Config) -> RenameResult<()> {
+        if !(config.workspace_edit_document_changes() && config.workspace_edit_rename_files()) {
+            bail!(
+                "Cannot rename `{}`, the editor does not support renaming files",
+                self.tag_name()
+            );
+        }
+        Ok(())
+    }
+
+    /// Only the class itself is renamed, so other PHP files that import it,
+    /// name it in full or register it under an alias would be left pointing
+    /// at a class that no longer exists
+    fn check_class_references(&self, db: &RootDatabase, root: &WorkspaceRoot) -> RenameResult<()> {
+        let Some(class_doc) = self
+            .defining_files(db, root)?
+            .into_iter()
+            .filter(|path| FileType::from_path(path) == Some(FileType::PHP))
+            .find_map(|path| db.parsed_document(&path))
+        else {
+            return Ok(());
+        };
+        let class_name = self.class_name();
+        let name = class_name.rsplit('\\').next().unwrap_or(&class_name);
+        let Some(class) = PhpFile::for_document(db, &class_doc)
+            .classes
+            .iter()
+            .find(|class| {
+                class
+                    .rsplit('\\')
+                    .next()
+                    .is_some_and(|last| last.eq_ignore_ascii_case(name))
+            })
+            .cloned()
+        else {
+            return Ok(());
+        };
+        let is_registered = providers::registrations(db, root)
+            .iter()
+            .any(|registration| match registration {
+                Registration::Alias { class: aliased, .. } => aliased.eq_ignore_ascii_case(&class),
+                _ => false,
+            });
+        if is_registered {
+            bail!(
+                "Cannot rename `{}`, `{class}` is registered by a service provider",
+                self.tag_name()
+            );
+        }
+        let lowercase = class.to_lowercase();
+        let referencing = db
+            .all_documents()
+            .iter()
+            .filter(|doc| doc.filetype == FileType::PHP && doc.source != class_doc.source)
+            .find(|doc| {
+                let imports = PhpFile::for_document(db, doc)
+                    .imports
+                    .iter()
+                    .any(|(_, import)| import.eq_ignore_ascii_case(&class));
+                imports || mentions_class(&doc.contents(db).to_lowercase(), &lowercase)
+            })
+            .map(|doc| doc.source.path(db).to_owned());
+        if let Some(path) = referencing {
+            bail!(
+                "Cannot rename `{}`, `{class}` is referenced in {path}",
+                self.tag_name()
+            );
+        }
+        Ok(())
+    }
+
+    /// Where each defining file moves to, under the same directory it was
+    /// found in
+    fn file_moves(
+        &self,
+        db: &RootDatabase,
+        root: &WorkspaceRoot,
+        new_target: &Target,
+    ) -> RenameResult<Vec<(Utf8PathBuf, Utf8PathBuf)>> {
+        self.defining_files(db, root)?
+            .into_iter()
+            .map(|src| match self.moved_path(db, root, &src, new_target) {
+                Some(dst) => Ok((src, dst)),
+                None => bail!(
+                    "`{}` is registered by a service provider and cannot be renamed",
+                    self.tag_name()
+                ),
+            })
+            .collect()
+    }
+
+    fn moved_path(
+        &self,
+        db: &RootDatabase,
+        root: &WorkspaceRoot,
+        path: &Utf8Path,
+        new_target: &Target,
+    ) -> Option<Utf8PathBuf> {
+        let is_class = FileType::from_path(path) == Some(FileType::PHP);
+        match (self, new_target) {
+            (Target::Component(name), Target::Component(new_name)) if is_class => root
+                .component_class_roots()
+                .find(|dir| resolve_path::component_class_path(name.path(), dir) == path)
+                .map(|dir| resolve_path::component_class_path(new_name.path(), &dir)),
+            (Target::Component(name), Target::Component(new_name)) => {
+                let anonymous_paths = providers::registrations(db, root)
+                    .iter()
+                    .filter_map(|registration| match registration {
+                        Registration::AnonymousPath {
+                            prefix: None,
+                            directory,
+                        } => Some(directory.clone()),
+                        _ => None,
+                    })
+                    .collect::<Vec<_>>();
+                // Keep the file the same kind of candidate, e.g.
+                // `input/index.blade.php` becomes `field/index.blade.php`
+                root.component_views_roots()
+                    .chain(anonymous_paths)
+                    .find_map(|dir| {
+                        let candidates = resolve_path::component_resources_paths(name.path(), &dir);
+                        let ix = candidates.iter().position(|candidate| candidate == path)?;
+                        resolve_path::component_resources_paths(new_name.path(), &dir)
+                            .into_iter()
+                            .nth(ix)
+                    })
+            }
+            (Target::Layout(name), Target::Layout(new_name)) if is_class => root
+                .component_class_roots()
+                .find(|dir| resolve_path::layout_class_path(name, dir) == path)
+                .map(|dir| resolve_path::layout_class_path(new_name, &dir)),
+            (Target::Layout(name), Target::Layout(new_name)) => root
+                .view_roots()
+                .find(|dir| resolve_path::layout_resources_path(name, dir) == path)
+                .map(|dir| resolve_path::layout_resources_path(new_name, &dir)),
+            _ => None,
+        }
+    }
+
+    /// `Forms\Input` and `AppLayout`, relative to the class root
+    fn class_name(&self) -> String {
+        match self {
+            Target::Component(name) => resolve_path::component_class_name(&name.path(), "\\"),
+            Target::Layout(name) => name.class_name(),
+        }
+    }
+
+    /// Renames the class declared in `path` and moves it to the namespace
+    /// that matches its new directory. The view its `render` method returns
+    /// is updated too, as long as it is named after the template.
+    fn class_edit(
+        &self,
+        db: &RootDatabase,
+        path: &Utf8Path,
+        new_target: &Target,
+        views: Option<(String, String)>,
+    ) -> TextEditBuilder {
+        let mut builder = TextEdit::builder();
+        let Some(doc) = db.parsed_document(path) else {
+            return builder;
+        };
+        let class_name = self.class_name();
+        let new_class_name = new_target.class_name();
+        let (namespace, name) = class_name
+            .rsplit_once('\\')
+            .unwrap_or(("", class_name.as_str()));
+        let (new_namespace, new_name) = new_class_name
+            .rsplit_once('\\')
+            .unwrap_or(("", new_class_name.as_str()));
+
+        for node in doc.root_node().descendants() {
+            if node.is::<ast::php::NamespaceDefinition>()
+                && namespace != new_namespace
+                && let Some(node) = field(node, "name")
+                && let Some(text) = doc.text_for_node(db, node)
+            {
+                let base = if namespace.is_empty() {
+                    Some(text)
+                } else {
+                    text.strip_suffix(namespace)
+                        .and_then(|base| base.strip_suffix('\\'))
+                };
+                if let Some(base) = base {
+                    let new_text = [base, new_namespace]
+                        .into_iter()
+                        .filter(|segment| !segment.is_empty())
+                        .join("\\");
+                    builder.replace(node_range(node), new_text);
+                }
+            } else if node.is::<ast::php::ClassDeclaration>()
+                && let Some(node) = field(node, "name")
+                && doc
+                    .text_for_node(db, node)
+                    .is_some_and(|text| text.eq_ignore_ascii_case(name))
+            {
+                builder.replace(node_range(node), new_name.to_owned());
+            } else if node.is::<ast::php::StringContent>()
+                && let Some((view, new_view)) = &views
+                && doc.text_for_node(db, node) == Some(view.as_str())
+                && is_rendered_view(db, &doc, node)
+            {
+                builder.replace(node_range(node), new_view.clone());
+            }
+        }
+        builder
+    }
+}
+
+/// Whether `text` names `class` in full, rather than a class whose name
+/// merely starts the same, e.g. `InputGroup` for `Input`
+fn mentions_class(text: &str, class: &str) -> bool {
+    text.match_indices(class).any(|(start, _)| {
+        !text[start + class.len()..].starts_with(|c: char| c.is_ascii_alphanumeric() || c == '_')
+    })
+}
+
+/// Whether a string is returned from the `render` method of a class
+fn is_rendered_view(db: &RootDatabase, doc: &ParsedDocument, node: UntypedNode<'_>) -> bool {
+    node.ancestors()
+        .any(|node| node.is::<ast::php::ReturnStatement>())
+        && node
+            .ancestors()
+            .find(|node| node.is::<ast::php::MethodDeclaration>())
+            .and_then(|method| field(method, "name"))
+            .and_then(|name| doc.text_for_node(db, name))
+            == Some("render")
+}
+
+/// The dotted name of a template, e.g. `components.forms.input`
+fn view_name(root: &WorkspaceRoot, template: &Utf8Path) -> Option<String> {
+    root.view_roots().find_map(|dir| {
+        let relative = template.strip_prefix(&dir).ok()?;
+        let name = relative.as_str().strip_suffix(".blade.php")?;
+        Some(name.replace(['\\', '/'], "."))
+    })
+}
+
+#[cfg(test)]
+mod tests;
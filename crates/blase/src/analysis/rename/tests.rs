@@ -0,0 +1,603 @@
+use std::sync::LazyLock;
+
+use async_lsp::lsp_types::{
+    ClientCapabilities, ResourceOperationKind, WorkspaceClientCapabilities,
+    WorkspaceEditClientCapabilities,
+};
+use expect_test::{Expect, expect};
+use itertools::Itertools;
+
+use crate::{analysis::fixture, config::Config, db::source_change::FileSystemEdit};
+
+const TEST_CONFIG: LazyLock<Config> = LazyLock::new(|| {
+    test_config(ClientCapabilities {
+        workspace: Some(WorkspaceClientCapabilities {
+            workspace_edit: Some(WorkspaceEditClientCapabilities {
+                document_changes: Some(true),
+                resource_operations: Some(vec![ResourceOperationKind::Rename]),
+                ..Default::default()
+            }),
+            ..Default::default()
+        }),
+        ..Default::default()
+    })
+});
+
+const NO_FILE_RENAMES_CONFIG: LazyLock<Config> =
+    LazyLock::new(|| test_config(ClientCapabilities::default()));
+
+fn test_config(capabilities: ClientCapabilities) -> Config {
+    Config {
+        capabilities,
+        roots: vec![crate::config::WorkspaceRoot::new(
+            "test".to_string(),
+            camino::Utf8PathBuf::from_path_buf(std::path::PathBuf::from("/")).unwrap(),
+        )],
+        client_info: None,
+        settings: Default::default(),
+    }
+}
+
+fn check_prepare(fixture: &str, expect: Expect) {
+    check_prepare_with(&TEST_CONFIG, fixture, expect)
+}
+
+fn check_prepare_with(config: &Config, fixture: &str, expect: Expect) {
+    let (analysis, position) = fixture::position(fixture);
+    let path = position.path.clone();
+    let actual = match analysis.prepare_rename(config, position).unwrap() {
+        Ok(range) => {
+            let contents = analysis.contents(&path).unwrap();
+            format!("{range:?} {}", &contents[range])
+        }
+        Err(err) => format!("error: {err}"),
+    };
+    expect.assert_eq(&actual)
+}
+
+fn check(new_name: &str, fixture: &str, expect: Expect) {
+    check_with(&TEST_CONFIG, new_name, fixture, expect)
+}
+
+fn check_with(config: &Config, new_name: &str, fixture: &str, expect: Expect) {
+    let (analysis, position) = fixture::position(fixture);
+    let change = match analysis.rename(config, position, new_name).unwrap() {
+        Ok(change) => change,
+        Err(err) => return expect.assert_eq(&format!("error: {err}")),
+    };
+
+    let mut actual = String::new();
+    let edits = change
+        .source_file_edits
+        .into_iter()
+        .sorted_by(|(a, _), (b, _)| a.cmp(b));
+    for (path, edit) in edits {
+        let mut contents = analysis.contents(&path).unwrap().to_owned();
+        edit.apply(&mut contents);
+        macros::format_to!(actual, "{path}\n{contents}\n");
+    }
+    for edit in change.file_system_edits {
+        match edit {
+            FileSystemEdit::MoveFile { src, dst } => {
+                macros::format_to!(actual, "{src} -> {dst}\n");
+            }
+        }
+    }
+
+    expect.assert_eq(&actual)
+}
+
+#[test]
+fn prepare_component() {
+    check_prepare(
+        r#"
+//- /resources/views/components/alert.blade.php
+<div></div>
+//- /resources/views/index.blade.php
+<x-al$0ert/>
+"#,
+        expect!["3..8 alert"],
+    );
+}
+
+#[test]
+fn prepare_component_without_file_renames() {
+    check_prepare_with(
+        &NO_FILE_RENAMES_CONFIG,
+        r#"
+//- /resources/views/components/alert.blade.php
+<div></div>
+//- /resources/views/index.blade.php
+<x-al$0ert/>
+"#,
+        expect!["error: Cannot rename `x-alert`, the editor does not support renaming files"],
+    );
+}
+
+#[test]
+fn prepare_html_element() {
+    check_prepare(
+        r#"
+<di$0v></div>
+"#,
+        expect!["error: Only components and layouts can be renamed"],
+    );
+}
+
+#[test]
+fn prepare_undefined_component() {
+    check_prepare(
+        r#"
+<x-al$0ert/>
+"#,
+        expect!["error: Cannot find the definition of `x-alert`"],
+    );
+}
+
+#[test]
+fn rename_anonymous_component() {
+    check(
+        "x-notice",
+        r#"
+//- /resources/views/components/alert.blade.php
+<div></div>
+//- /resources/views/index.blade.php
+<x-al$0ert/>
+<x-alert>
+    <x-alert-icon/>
+</x-alert>
+"#,
+        expect![[r#"
+            /resources/views/index.blade.php
+            <x-notice/>
+            <x-notice>
+                <x-alert-icon/>
+            </x-notice>
+
+            /resources/views\components\alert.blade.php -> /resources/views\components\notice.blade.php
+        "#]],
+    );
+}
+
+#[test]
+fn rename_component_without_file_renames() {
+    check_with(
+        &NO_FILE_RENAMES_CONFIG,
+        "x-notice",
+        r#"
+//- /resources/views/components/alert.blade.php
+<div></div>
+//- /resources/views/index.blade.php
+<x-al$0ert/>
+"#,
+        expect!["error: Cannot rename `x-alert`, the editor does not support renaming files"],
+    );
+}
+
+#[test]
+fn rename_from_end_tag() {
+    check(
+        "notice",
+        r#"
+//- /resources/views/components/alert.blade.php
+<div></div>
+//- /resources/views/index.blade.php
+<x-alert>
+</x-al$0ert>
+"#,
+        expect![[r#"
+            /resources/views/index.blade.php
+            <x-notice>
+            </x-notice>
+
+            /resources/views\components\alert.blade.php -> /resources/views\components\notice.blade.php
+        "#]],
+    );
+}
+
+#[test]
+fn rename_index_component() {
+    check(
+        "tabs",
+        r#"
+//- /resources/views/components/accordion/index.blade.php
+<div></div>
+//- /resources/views/index.blade.php
+<x-acc$0ordion/>
+"#,
+        expect![[r#"
+            /resources/views/index.blade.php
+            <x-tabs/>
+
+            /resources/views\components\accordion\index.blade.php -> /resources/views\components\tabs\index.blade.php
+        "#]],
+    );
+}
+
+#[test]
+fn rename_class_component() {
+    check(
+        "inputs.text",
+        r#"
+//- /app/View/Components/Forms/Input.php
+<?php
+
+namespace App\View\Components\Forms;
+
+use Illuminate\View\Component;
+
+class Input extends Component
+{
+    public function render()
+    {
+        return view('components.forms.input');
+    }
+}
+//- /resources/views/components/forms/input.blade.php
+<input {{ $attributes }}>
+//- /resources/views/index.blade.php
+<x-forms.inp$0ut/>
+<x-forms.input-group/>
+"#,
+        expect![[r#"
+            /app/View/Components\Forms\Input.php
+            <?php
+
+            namespace App\View\Components\Inputs;
+
+            use Illuminate\View\Component;
+
+            class Text extends Component
+            {
+                public function render()
+                {
+                    return view('components.inputs.text');
+                }
+            }
+
+            /resources/views/index.blade.php
+            <x-inputs.text/>
+            <x-forms.input-group/>
+
+            /app/View/Components\Forms\Input.php -> /app/View/Components\Inputs\Text.php
+            /resources/views\components\forms\input.blade.php -> /resources/views\components\inputs\text.blade.php
+        "#]],
+    );
+}
+
+#[test]
+fn rename_class_component_keeps_other_strings() {
+    check(
+        "notice",
+        r#"
+//- /app/View/Components/Alert.php
+<?php
+
+namespace App\View\Components;
+
+use Illuminate\View\Component;
+
+class Alert extends Component
+{
+    public string $cacheKey = 'components.alert';
+
+    public function render()
+    {
+        return view('components.alert');
+    }
+}
+//- /resources/views/components/alert.blade.php
+<div {{ $attributes }}></div>
+//- /resources/views/index.blade.php
+<x-al$0ert/>
+"#,
+        expect![[r#"
+            /app/View/Components\Alert.php
+            <?php
+
+            namespace App\View\Components;
+
+            use Illuminate\View\Component;
+
+            class Notice extends Component
+            {
+                public string $cacheKey = 'components.alert';
+
+                public function render()
+                {
+                    return view('components.notice');
+                }
+            }
+
+            /resources/views/index.blade.php
+            <x-notice/>
+
+            /app/View/Components\Alert.php -> /app/View/Components\Notice.php
+            /resources/views\components\alert.blade.php -> /resources/views\components\notice.blade.php
+        "#]],
+    );
+}
+
+#[test]
+fn rename_class_component_imported_elsewhere() {
+    check(
+        "notice",
+        r#"
+//- /app/View/Components/Alert.php
+<?php
+
+namespace App\View\Components;
+
+class Alert extends Component
+{
+    public function render()
+    {
+        return view('components.alert');
+    }
+}
+//- /app/Http/Controllers/HomeController.php
+<?php
+
+namespace App\Http\Controllers;
+
+use App\View\Components\Alert;
+
+class HomeController
+{
+}
+//- /resources/views/components/alert.blade.php
+<div></div>
+//- /resources/views/index.blade.php
+<x-al$0ert/>
+"#,
+        expect![[
+            r#"error: Cannot rename `x-alert`, `App\View\Components\Alert` is referenced in /app/Http/Controllers/HomeController.php"#
+        ]],
+    );
+}
+
+#[test]
+fn rename_layout() {
+    check(
+        "x-admin-layout",
+        r#"
+//- /app/View/Components/AppLayout.php
+<?php
+
+namespace App\View\Components;
+
+use Illuminate\View\Component;
+
+class AppLayout extends Component
+{
+    public function render()
+    {
+        return view('layouts.app');
+    }
+}
+//- /resources/views/layouts/app.blade.php
+<html>{{ $slot }}</html>
+//- /resources/views/index.blade.php
+<x-app-la$0yout>
+</x-app-layout>
+"#,
+        expect![[r#"
+            /app/View/Components\AppLayout.php
+            <?php
+
+            namespace App\View\Components;
+
+            use Illuminate\View\Component;
+
+            class AdminLayout extends Component
+            {
+                public function render()
+                {
+                    return view('layouts.admin');
+                }
+            }
+
+            /resources/views/index.blade.php
+            <x-admin-layout>
+            </x-admin-layout>
+
+            /app/View/Components\AppLayout.php -> /app/View/Components\AdminLayout.php
+            /resources/views\layouts\app.blade.php -> /resources/views\layouts\admin.blade.php
+        "#]],
+    );
+}
+
+#[test]
+fn rename_component_into_layout() {
+    check(
+        "admin-layout",
+        r#"
+//- /resources/views/components/alert.blade.php
+<div></div>
+//- /resources/views/index.blade.php
+<x-al$0ert/>
+"#,
+        expect!["error: `x-admin-layout` is not a valid component name"],
+    );
+}
+
+#[test]
+fn rename_to_existing_component() {
+    check(
+        "notice",
+        r#"
+//- /resources/views/components/alert.blade.php
+<div></div>
+//- /resources/views/components/notice.blade.php
+<div></div>
+//- /resources/views/index.blade.php
+<x-al$0ert/>
+"#,
+        expect![[
+            r#"error: Cannot rename to `x-notice`, /resources/views\components\notice.blade.php already exists"#
+        ]],
+    );
+}
+
+#[test]
+fn rename_namespaced_component() {
+    check(
+        "notice",
+        r#"
+//- /resources/views/index.blade.php
+<x-courier::al$0ert/>
+"#,
+        expect!["error: `x-courier::alert` belongs to a package and cannot be renamed"],
+    );
+}
+
+#[test]
+fn prepare_prop() {
+    check_prepare(
+        r#"
+//- /resources/views/components/alert.blade.php
+@props(['ti$0tle' => null])
+"#,
+        expect!["9..14 title"],
+    );
+}
+
+#[test]
+fn rename_anonymous_prop() {
+    check(
+        "heading",
+        r#"
+//- /resources/views/components/alert.blade.php
+@props(['ti$0tle' => null, 'type'])
+<div>{{ $title }}</div>
+<x-icon :$title/>
+//- /resources/views/index.blade.php
+<x-alert title="Hi"/>
+<x-alert :title="$heading" type="info"></x-alert>
+<x-alert :$title/>
+<x-alert-banner title="Hi"/>
+"#,
+        expect![[r#"
+            /resources/views/components/alert.blade.php
+            @props(['heading' => null, 'type'])
+            <div>{{ $heading }}</div>
+            <x-icon :title="$heading"/>
+
+            /resources/views/index.blade.php
+            <x-alert heading="Hi"/>
+            <x-alert :heading="$heading" type="info"></x-alert>
+            <x-alert :heading="$title"/>
+            <x-alert-banner title="Hi"/>
+
+        "#]],
+    );
+}
+
+#[test]
+fn rename_promoted_parameter() {
+    check(
+        "$heading",
+        r#"
+//- /app/View/Components/Alert.php
+<?php
+
+namespace App\View\Components;
+
+class Alert extends Component
+{
+    public function __construct(
+        public string $ti$0tle,
+    ) {}
+
+    public function shout()
+    {
+        return strtoupper($this->title);
+    }
+}
+//- /resources/views/components/alert.blade.php
+<div>{{ $title }}</div>
+//- /resources/views/index.blade.php
+<x-alert title="Hi"/>
+"#,
+        expect![[r#"
+            /app/View/Components\Alert.php
+            <?php
+
+            namespace App\View\Components;
+
+            class Alert extends Component
+            {
+                public function __construct(
+                    public string $heading,
+                ) {}
+
+                public function shout()
+                {
+                    return strtoupper($this->heading);
+                }
+            }
+
+            /resources/views/components/alert.blade.php
+            <div>{{ $heading }}</div>
+
+            /resources/views/index.blade.php
+            <x-alert heading="Hi"/>
+
+        "#]],
+    );
+}
+
+#[test]
+fn rename_constructor_parameter() {
+    check(
+        "variant",
+        r#"
+//- /app/View/Components/Alert.php
+<?php
+
+namespace App\View\Components;
+
+class Alert extends Component
+{
+    public function __construct(string $ty$0pe = 'info')
+    {
+        $this->kind = $type;
+    }
+}
+//- /resources/views/components/alert.blade.php
+<div class="{{ $kind }}"></div>
+//- /resources/views/index.blade.php
+<x-alert type="error"/>
+"#,
+        expect![[r#"
+            /app/View/Components\Alert.php
+            <?php
+
+            namespace App\View\Components;
+
+            class Alert extends Component
+            {
+                public function __construct(string $variant = 'info')
+                {
+                    $this->kind = $variant;
+                }
+            }
+
+            /resources/views/index.blade.php
+            <x-alert variant="error"/>
+
+        "#]],
+    );
+}
+
+#[test]
+fn rename_prop_to_invalid_name() {
+    check(
+        "my-title",
+        r#"
+//- /resources/views/components/alert.blade.php
+@props(['ti$0tle'])
+"#,
+        expect!["error: `my-title` is not a valid attribute name"],
+    );
+}
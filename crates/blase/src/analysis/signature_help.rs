@@ -1,12 +1,12 @@
 use line_index::{TextRange, TextSize};
 use macros::format_to;
-use type_sitter::{HasChildren, Node};
+use type_sitter::Node;
 
 use crate::{
-    config::Config,
+    config::{Config, WorkspaceRoot},
     db::{
         FilePosition, ParsedDocument,
-        def::{Component, DefDatabase},
+        def::{Component, ComponentAttr, DefDatabase, class_component::ClassComponent},
     },
 };
 
@@ -15,6 +15,8 @@ pub struct SignatureHelp {
     pub signature: String,
     pub active_parameter: Option<usize>,
     parameters: Vec<TextRange>,
+    /// Markdown documentation of each parameter
+    parameter_docs: Vec<Option<String>>,
 }
 
 impl SignatureHelp {
@@ -26,13 +28,18 @@ impl SignatureHelp {
         &self.parameters
     }
 
-    fn push_attr(&mut self, name: &str, default: Option<&str>) {
+    pub fn parameter_docs(&self) -> &[Option<String>] {
+        &self.parameter_docs
+    }
+
+    fn push_attr(&mut self, name: &str, default: Option<&str>, docs: Option<String>) {
         self.signature.push(' ');
         let start = TextSize::of(&self.signature);
         self.signature.push_str(name);
         format_to!(self.signature, "=\"{}\"", default.unwrap_or_default());
         let end = TextSize::of(&self.signature);
-        self.parameters.push(TextRange::new(start, end))
+        self.parameters.push(TextRange::new(start, end));
+        self.parameter_docs.push(docs);
     }
 }
 
@@ -41,13 +48,14 @@ pub fn signature_help(
     config: &Config,
     FilePosition { path, offset }: FilePosition,
 ) -> Option<SignatureHelp> {
-    let document = &db.parsed_document(&path)?;
+    let root = config.root_for(&path);
+    let document = &ClassComponent::document_at(db, db.parsed_document(&path)?, offset, root);
     let node = document.get_node_at(offset)?;
 
     let ancestors = std::iter::successors(Some(node), Node::parent);
     for ancestor in ancestors {
         ast::match_node!(ancestor, {
-            ast::blade::Attribute(attr) => return signature_help_for_attr(db, attr, document, config),
+            ast::blade::Attribute(attr) => return signature_help_for_attr(db, attr, document, root),
             _ => ()
         })
     }
@@ -62,44 +70,10 @@ impl Component {
         attr: &ast::blade::Attribute,
         doc: &ParsedDocument,
     ) -> Option<usize> {
-        let attr_name = match attr {
-            ast::blade::Attribute::ExpressionAttribute(expression_attribute) => expression_attribute.children(&mut expression_attribute.walk()).filter_map(|ch| {
-                let ch = ch.ok()?;
-                match ch {
-                    ast::blade::anon_unions::ExpressionAttributeName_QuotedExpression::ExpressionAttributeName(attribute_name) => Some(attribute_name.upcast()),
-                    ast::blade::anon_unions::ExpressionAttributeName_QuotedExpression::QuotedExpression(_) => None,
-                }
-            }).last(),
-            ast::blade::Attribute::HtmlAttribute(html_attribute) => html_attribute.children(&mut html_attribute.walk()).filter_map(|ch| {
-                let ch = ch.ok()?;
-                match ch {
-                    ast::blade::anon_unions::AttributeName_AttributeValue_QuotedAttributeValue::AttributeName(attribute_name) => Some(attribute_name.upcast()),
-                    ast::blade::anon_unions::AttributeName_AttributeValue_QuotedAttributeValue::AttributeValue(_) |
-                    ast::blade::anon_unions::AttributeName_AttributeValue_QuotedAttributeValue::QuotedAttributeValue(_) => None,
-                }
-            }).last(),
-            ast::blade::Attribute::ShortAttribute(short_attribute) => short_attribute.variable_name().ok().map(Node::upcast),
-            ast::blade::Attribute::BladeAttribute(_) |
-            ast::blade::Attribute::PhpStatement(_) |
-            ast::blade::Attribute::Comment(_) |
-            ast::blade::Attribute::Conditional(_) |
-            ast::blade::Attribute::Envoy(_) |
-            ast::blade::Attribute::InlineDirective(_) |
-            ast::blade::Attribute::Keyword(_) |
-            ast::blade::Attribute::Livewire(_) |
-            ast::blade::Attribute::Loops(_) |
-            ast::blade::Attribute::Props(_) |
-            ast::blade::Attribute::Switch(_) |
-            ast::blade::Attribute::WireUi(_) => None,
-        }?;
-        let attr_name = doc.text_for_node(db, attr_name)?;
-        let attr_name = attr_name
-            .strip_prefix(':') // expression attribute name
-            .or_else(|| attr_name.strip_prefix('$')) // short variable attribute
-            .unwrap_or(attr_name);
+        let (attr_name, _) = ComponentAttr::passed_name(db, doc, *attr)?;
         self.attrs(db)?
             .iter()
-            .position(|attr| attr.name.as_str() == attr_name)
+            .position(|attr| attr.is_passed_as(attr_name))
     }
 }
 
@@ -107,19 +81,24 @@ fn signature_help_for_attr(
     db: &dyn DefDatabase,
     attr: ast::blade::Attribute,
     document: &ParsedDocument,
-    config: &Config,
+    root: &WorkspaceRoot,
 ) -> Option<SignatureHelp> {
-    let component = Component::for_attr(db, attr, document, config)?;
+    let component = Component::for_attr(db, attr, document, root)?;
     let active_attr = component.active_attr(db, &attr, document);
     let mut res = SignatureHelp {
         signature: String::new(),
         active_parameter: active_attr,
         parameters: Vec::new(),
+        parameter_docs: Vec::new(),
     };
     format_to!(res.signature, "<x-{}", component.name(db));
     if let Some(attrs) = component.attrs(db) {
         for attr in attrs.as_ref() {
-            res.push_attr(attr.name.as_str(), attr.default_value.as_deref());
+            res.push_attr(
+                &attr.tag_name(),
+                attr.default_value.as_deref(),
+                attr.documentation(),
+            );
         }
     }
     res.signature.push('>');
@@ -138,9 +117,12 @@ mod test {
 
     const TEST_CONFIG: LazyLock<Config> = LazyLock::new(|| Config {
         capabilities: async_lsp::lsp_types::ClientCapabilities::default(),
-        workspace_folder: camino::Utf8PathBuf::from_path_buf(std::path::PathBuf::from("/"))
-            .unwrap(),
+        roots: vec![crate::config::WorkspaceRoot::new(
+            "test".to_string(),
+            camino::Utf8PathBuf::from_path_buf(std::path::PathBuf::from("/")).unwrap(),
+        )],
         client_info: None,
+        settings: Default::default(),
     });
 
     fn check(blase_fixture: &str, expected_render: Expect, expected_labels: Expect) {
@@ -177,6 +159,15 @@ mod test {
         expected_labels.assert_eq(&labels);
     }
 
+    fn check_docs(blase_fixture: &str, expect: Expect) {
+        let (analysis, position) = fixture::position(blase_fixture);
+        let help = analysis.signature_help(&TEST_CONFIG, position).unwrap();
+        let docs = help
+            .and_then(|help| help.parameter_docs()[help.active_parameter?].clone())
+            .unwrap_or_default();
+        expect.assert_eq(&docs);
+    }
+
     #[test]
     fn short_attribute_signature_help() {
         check(
@@ -247,6 +238,125 @@ mod test {
         );
     }
 
+    #[test]
+    fn kebab_case_attribute_signature_help() {
+        check(
+            r#"
+//- /resources/views/components/badge.blade.php
+@props(['color', 'dueDate'])
+
+//- /resources/views/index.blade.php
+<x-badge due-da$0te=""/>
+            "#,
+            expect![[r#"
+                <x-badge color="" due-date="">
+                ------------------^^^^^^^^^^^^"#]],
+            expect![[r#"
+                [
+                    color=""
+                    due-date=""
+                ]"#]],
+        );
+    }
+
+    #[test]
+    fn aliased_component_signature_help() {
+        check(
+            r#"
+//- /app/Providers/AppServiceProvider.php
+<?php
+
+namespace App\Providers;
+
+use App\Support\Alert;
+use Illuminate\Support\Facades\Blade;
+use Illuminate\Support\ServiceProvider;
+
+class AppServiceProvider extends ServiceProvider
+{
+    public function boot(): void
+    {
+        Blade::component('alert', Alert::class);
+    }
+}
+//- /app/Support/Alert.php
+<?php
+
+namespace App\Support;
+
+class Alert extends Component
+{
+    public function __construct(
+        public string $type,
+        public string $message = 'Saved',
+    ){}
+}
+//- /resources/views/index.blade.php
+<x-alert mess$0age=""/>
+            "#,
+            expect![[r#"
+                <x-alert type="" message="'Saved'">
+                -----------------^^^^^^^^^^^^^^^^^^"#]],
+            expect![[r#"
+                [
+                    type=""
+                    message="'Saved'"
+                ]"#]],
+        );
+    }
+
+    #[test]
+    fn typed_attribute_signature_help_docs() {
+        check_docs(
+            r#"
+//- /app/View/Components/AlertType.php
+<?php
+
+namespace App\View\Components;
+
+enum AlertType: string
+{
+    case Info = 'info';
+}
+//- /app/View/Components/Alert.php
+<?php
+
+namespace App\View\Components;
+
+class Alert extends Component
+{
+    /**
+     * @param AlertType $type The kind of alert
+     */
+    public function __construct(
+        public AlertType $type,
+        public int $timeout = 5,
+    ){}
+}
+//- /resources/views/index.blade.php
+<x-alert ty$0pe=""/>
+            "#,
+            expect![[r#"
+                `AlertType`, required
+
+                The kind of alert"#]],
+        );
+    }
+
+    #[test]
+    fn anonymous_attribute_signature_help_docs() {
+        check_docs(
+            r#"
+//- /resources/views/components/index-table.blade.php
+@props(['subject', 'rows' => []])
+
+//- /resources/views/index.blade.php
+<x-index-table :ro$0ws="$units"/>
+            "#,
+            expect!["defaults to `[]`"],
+        );
+    }
+
     #[test]
     fn no_signature_help() {
         check(
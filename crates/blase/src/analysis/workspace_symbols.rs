@@ -1,12 +1,10 @@
 use std::ops::Not;
 
-use camino::Utf8Path;
-
 use crate::{
-    config::Config,
+    config::{Config, WorkspaceRoot},
     db::{
         DocumentDatabase, FileRange, ParsedDocument, RootDatabase,
-        def::{ComponentName, LayoutName, ViewName},
+        def::{ComponentName, LayoutName, ViewName, attributes},
     },
 };
 
@@ -30,7 +28,10 @@ pub fn workspace_symbols(
     let symbols = db
         .all_documents()
         .into_iter()
-        .filter_map(|doc| filter_document(db, doc, &query, &config.workspace_folder))
+        .filter_map(|doc| {
+            let root = config.root_for(doc.source.path(db));
+            filter_document(db, doc, &query, root)
+        })
         .collect::<Vec<_>>();
 
     symbols.is_empty().not().then_some(symbols)
@@ -40,30 +41,30 @@ fn filter_document(
     db: &dyn DocumentDatabase,
     doc: &ParsedDocument,
     query: &str,
-    ws_path: &Utf8Path,
+    root: &WorkspaceRoot,
 ) -> Option<SymbolInformation> {
-    if let Some(name) = ComponentName::from_document(db, doc, ws_path) {
+    if let Some(name) = ComponentName::from_document(db, doc, root) {
         let tag_name = name.tag_name();
         if tag_name.contains(query) {
             let info = SymbolInformation {
                 name: tag_name,
                 range: FileRange {
                     path: doc.source.path(db).to_path_buf(),
-                    range: Default::default(),
+                    range: attributes::definition_range(doc),
                 },
                 kind: SymbolKind::Component,
             };
             return Some(info);
         }
     }
-    if let Some(name) = LayoutName::from_document(db, doc, ws_path) {
+    if let Some(name) = LayoutName::from_document(db, doc, root) {
         let tag_name = name.tag_name();
         if tag_name.contains(query) {
             let info = SymbolInformation {
                 name: tag_name,
                 range: FileRange {
                     path: doc.source.path(db).to_path_buf(),
-                    range: Default::default(),
+                    range: attributes::definition_range(doc),
                 },
                 kind: SymbolKind::Layout,
             };
@@ -71,7 +72,7 @@ fn filter_document(
         }
     }
 
-    if let Some(name) = ViewName::from_document(db, doc, ws_path) {
+    if let Some(name) = ViewName::from_document(db, doc, root) {
         let tag_name = name.as_str();
         if tag_name.contains(query) {
             let info = SymbolInformation {
@@ -7,8 +7,12 @@ use crate::{analysis::fixture, config::Config};
 
 const TEST_CONFIG: LazyLock<Config> = LazyLock::new(|| Config {
     capabilities: async_lsp::lsp_types::ClientCapabilities::default(),
-    workspace_folder: camino::Utf8PathBuf::from_path_buf(std::path::PathBuf::from("/")).unwrap(),
+    roots: vec![crate::config::WorkspaceRoot::new(
+        "test".to_string(),
+        camino::Utf8PathBuf::from_path_buf(std::path::PathBuf::from("/")).unwrap(),
+    )],
     client_info: None,
+    settings: Default::default(),
 });
 
 fn check(fixture: &str, query: &str, expect: Expect) {
@@ -28,6 +32,9 @@ fn check(fixture: &str, query: &str, expect: Expect) {
                     SymbolKind::Layout => "layout",
                 };
                 macros::format_to!(buf, "\n\n({}) <{}/> {}", kind, info.name, info.range.path);
+                if !info.range.range.is_empty() {
+                    macros::format_to!(buf, " {:?}", info.range.range);
+                }
             }
             buf
         }
@@ -63,11 +70,11 @@ $0
         "#,
         "a",
         expect![[r#"
-            (component) <x-bar/> /resources/views/components/bar.blade.php
+            (component) <x-bar/> /resources/views/components/bar.blade.php 0..18
 
             (layout) <x-foo-layout/> /resources/views/layouts/foo.blade.php
 
-            (component) <x-index-table/> /resources/views/components/index-table.blade.php"#]],
+            (component) <x-index-table/> /resources/views/components/index-table.blade.php 0..50"#]],
     );
 }
 
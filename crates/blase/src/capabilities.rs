@@ -1,15 +1,20 @@
 use async_lsp::lsp_types::{
-    CompletionOptions, CompletionOptionsCompletionItem, HoverProviderCapability, OneOf,
-    PositionEncodingKind, SaveOptions, ServerCapabilities, SignatureHelpOptions,
+    CodeActionKind, CodeActionOptions, CodeActionProviderCapability, CompletionOptions,
+    CompletionOptionsCompletionItem, DidChangeWatchedFilesRegistrationOptions, FileSystemWatcher,
+    GlobPattern, HoverProviderCapability, OneOf, PositionEncodingKind, RelativePattern,
+    RenameOptions, ResourceOperationKind, SaveOptions, ServerCapabilities, SignatureHelpOptions,
     TextDocumentSyncCapability, TextDocumentSyncKind, TextDocumentSyncOptions,
-    WorkDoneProgressOptions,
+    WorkDoneProgressOptions, WorkspaceFoldersServerCapabilities, WorkspaceServerCapabilities,
 };
+use camino::{Utf8Path, Utf8PathBuf};
+use itertools::Itertools;
 use line_index::WideEncoding;
 
 use crate::{
     analysis::completions::{self /*CompletionFieldsToResolve*/},
     config::Config,
     line_index::PositionEncoding,
+    lsp, resolve_path,
 };
 
 impl Config {
@@ -114,6 +119,100 @@ impl Config {
         .unwrap_or_default();
         caps.did_save == Some(true) && caps.dynamic_registration == Some(true)
     }
+
+    pub fn did_change_watched_files_dynamic_registration(&self) -> bool {
+        (|| {
+            self.capabilities
+                .workspace
+                .as_ref()?
+                .did_change_watched_files
+                .as_ref()?
+                .dynamic_registration
+        })()
+        .unwrap_or_default()
+    }
+
+    pub fn workspace_edit_document_changes(&self) -> bool {
+        (|| {
+            self.capabilities
+                .workspace
+                .as_ref()?
+                .workspace_edit
+                .as_ref()?
+                .document_changes
+        })()
+        .unwrap_or_default()
+    }
+
+    /// Whether files can be renamed as part of a workspace edit
+    pub fn workspace_edit_rename_files(&self) -> bool {
+        (|| {
+            let operations = self
+                .capabilities
+                .workspace
+                .as_ref()?
+                .workspace_edit
+                .as_ref()?
+                .resource_operations
+                .as_ref()?;
+            Some(operations.contains(&ResourceOperationKind::Rename))
+        })()
+        .unwrap_or_default()
+    }
+
+    fn did_change_watched_files_relative_pattern_support(&self) -> bool {
+        (|| {
+            self.capabilities
+                .workspace
+                .as_ref()?
+                .did_change_watched_files
+                .as_ref()?
+                .relative_pattern_support
+        })()
+        .unwrap_or_default()
+    }
+}
+
+/// Watches every Blade file of each root, wherever it appears, along with
+/// the PHP files of the application, its component classes and those of
+/// its packages. Roots are watched as configured, so that a `*` in them
+/// also matches directories created later on.
+pub fn file_watchers(config: &Config) -> DidChangeWatchedFilesRegistrationOptions {
+    let relative_patterns = config.did_change_watched_files_relative_pattern_support();
+    let watchers = config
+        .roots
+        .iter()
+        .flat_map(|root| {
+            let packages = Utf8Path::new(&resolve_path::vendor_dir()).join("*/*");
+            let php_dirs = [Utf8PathBuf::from(resolve_path::app_dir())]
+                .into_iter()
+                .chain(root.layout().component_class_roots.iter().cloned())
+                .chain([packages.join(resolve_path::package_component_class_dir())])
+                .map(|dir| {
+                    // Globs are separated by `/` alone
+                    let dir = dir.components().map(|c| c.as_str()).join("/");
+                    format!("{dir}/**/*.php")
+                });
+            std::iter::once("**/*.blade.php".to_owned())
+                .chain(php_dirs)
+                .unique()
+                .map(move |pattern| {
+                    if relative_patterns {
+                        GlobPattern::Relative(RelativePattern {
+                            base_uri: OneOf::Right(lsp::into_proto::url(&root.folder)),
+                            pattern,
+                        })
+                    } else {
+                        GlobPattern::String(format!("{}/{pattern}", root.folder))
+                    }
+                })
+        })
+        .map(|glob_pattern| FileSystemWatcher {
+            glob_pattern,
+            kind: None,
+        })
+        .collect();
+    DidChangeWatchedFilesRegistrationOptions { watchers }
 }
 
 pub fn server_capabilities(config: &Config) -> ServerCapabilities {
@@ -177,19 +276,37 @@ pub fn server_capabilities(config: &Config) -> ServerCapabilities {
             completion_item: config.completion_item(),
         }),
         references_provider: Some(OneOf::Left(true)),
+        rename_provider: Some(OneOf::Right(RenameOptions {
+            prepare_provider: Some(true),
+            work_done_progress_options: WorkDoneProgressOptions {
+                work_done_progress: None,
+            },
+        })),
+        code_action_provider: Some(CodeActionProviderCapability::Options(CodeActionOptions {
+            code_action_kinds: Some(vec![CodeActionKind::QUICKFIX]),
+            work_done_progress_options: WorkDoneProgressOptions {
+                work_done_progress: None,
+            },
+            resolve_provider: None,
+        })),
         workspace_symbol_provider: Some(OneOf::Left(true)),
+        workspace: Some(WorkspaceServerCapabilities {
+            workspace_folders: Some(WorkspaceFoldersServerCapabilities {
+                supported: Some(true),
+                change_notifications: Some(OneOf::Left(true)),
+            }),
+            file_operations: None,
+        }),
 
         // Methods below this line are unsupported
         document_formatting_provider: None,
         diagnostic_provider: None,
-        rename_provider: None,
         semantic_tokens_provider: None,
         selection_range_provider: None,
         type_definition_provider: None,
         implementation_provider: None,
         document_highlight_provider: None,
         document_symbol_provider: None,
-        code_action_provider: None,
         code_lens_provider: None,
         document_range_formatting_provider: None,
         document_on_type_formatting_provider: None,
@@ -198,7 +315,6 @@ pub fn server_capabilities(config: &Config) -> ServerCapabilities {
         folding_range_provider: None,
         declaration_provider: None,
         execute_command_provider: None,
-        workspace: None,
         call_hierarchy_provider: None,
         moniker_provider: None,
         linked_editing_range_provider: None,
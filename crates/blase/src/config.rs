@@ -1,10 +1,11 @@
-use async_lsp::lsp_types::{
-    ClientCapabilities, PositionEncodingKind,
-};
-use camino::Utf8PathBuf;
+use std::sync::LazyLock;
+
+use async_lsp::lsp_types::{ClientCapabilities, LSPAny, PositionEncodingKind};
+use camino::{Utf8Path, Utf8PathBuf};
 use line_index::WideEncoding;
+use smol_str::SmolStr;
 
-use crate::line_index::PositionEncoding;
+use crate::{line_index::PositionEncoding, resolve_path};
 
 #[derive(Clone, Debug)]
 pub struct ClientInfo {
@@ -13,8 +14,206 @@ pub struct ClientInfo {
 
 pub struct Config {
     pub capabilities: ClientCapabilities,
-    pub workspace_folder: Utf8PathBuf,
+    pub roots: Vec<WorkspaceRoot>,
     pub client_info: Option<ClientInfo>,
+    /// The latest settings from the client, which also apply to folders
+    /// added to the workspace later on
+    pub settings: LSPAny,
+}
+
+/// A workspace folder, which is a project of its own, such as a Laravel
+/// application or a package developed alongside it
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct WorkspaceRoot {
+    pub name: String,
+    pub folder: Utf8PathBuf,
+    layout: ProjectLayout,
+    /// The view roots of the layout with their `*` expanded, as of the last
+    /// time the layout was set or the roots were refreshed
+    view_dirs: Vec<Utf8PathBuf>,
+    /// The component class roots of the layout, expanded the same way
+    component_class_dirs: Vec<Utf8PathBuf>,
+}
+
+/// Where a project keeps its views and components. Directories are
+/// relative to the workspace folder.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct ProjectLayout {
+    /// Directories of views, each holding its anonymous components in
+    /// `components`. A `*` segment stands for every directory at that level.
+    pub view_roots: Vec<Utf8PathBuf>,
+    /// Directories of component classes, which may hold `*` segments as well
+    pub component_class_roots: Vec<Utf8PathBuf>,
+    /// Directories of components used with a prefix, e.g. `admin` for `<x-admin::panel>`
+    pub namespaces: Vec<(SmolStr, Utf8PathBuf)>,
+}
+
+impl Default for ProjectLayout {
+    fn default() -> Self {
+        Self {
+            view_roots: vec![resolve_path::views_dir().into()],
+            component_class_roots: vec![resolve_path::component_class_dir().into()],
+            namespaces: Vec::new(),
+        }
+    }
+}
+
+impl ProjectLayout {
+    /// Reads the layout from the initialization options or the settings of
+    /// the client, which may nest it under `blase`:
+    ///
+    /// ```json
+    /// {
+    ///     "viewRoots": ["resources/views", "modules/*/resources/views"],
+    ///     "componentClassRoots": ["app/View/Components"],
+    ///     "namespaces": { "admin": "modules/admin/resources/views/components" },
+    ///     "folders": { "courier": { "componentClassRoots": ["src/View/Components"] } }
+    /// }
+    /// ```
+    ///
+    /// A `*` stands for every directory at that level, which is looked up
+    /// when the layout is set and again whenever files are created or
+    /// deleted. Settings under `folders` apply only to the workspace
+    /// folder of that name. Settings that are left out keep their defaults.
+    /// Returns None if there are no settings at all.
+    pub fn from_settings(settings: &LSPAny, root: &WorkspaceRoot) -> Option<Self> {
+        let settings = settings.get("blase").unwrap_or(settings);
+        settings.as_object()?;
+        let mut layout = Self::default();
+        layout.apply(settings);
+        let folder_settings = settings
+            .get("folders")
+            .and_then(|folders| folders.get(&root.name));
+        if let Some(folder_settings) = folder_settings {
+            layout.apply(folder_settings);
+        }
+        Some(layout)
+    }
+
+    fn apply(&mut self, settings: &LSPAny) {
+        let roots = |key: &str| {
+            let patterns = settings.get(key)?.as_array()?;
+            let roots = patterns
+                .iter()
+                .filter_map(|pattern| pattern.as_str())
+                .map(Utf8PathBuf::from)
+                .collect::<Vec<_>>();
+            Some(roots)
+        };
+        if let Some(view_roots) = roots("viewRoots") {
+            self.view_roots = view_roots;
+        }
+        if let Some(class_roots) = roots("componentClassRoots") {
+            self.component_class_roots = class_roots;
+        }
+        if let Some(namespaces) = settings.get("namespaces").and_then(|ns| ns.as_object()) {
+            self.namespaces = namespaces
+                .iter()
+                .filter_map(|(prefix, dir)| Some((SmolStr::new(prefix), dir.as_str()?.into())))
+                .collect();
+        }
+    }
+}
+
+/// Expands the `*` segments of a directory pattern into the directories
+/// that exist in the workspace
+fn expand(workspace_folder: &Utf8Path, pattern: &Utf8Path) -> Vec<Utf8PathBuf> {
+    let mut dirs = vec![Utf8PathBuf::new()];
+    for segment in pattern
+        .as_str()
+        .split(['/', '\\'])
+        .filter(|s| !s.is_empty())
+    {
+        if segment != "*" {
+            dirs.iter_mut().for_each(|dir| dir.push(segment));
+            continue;
+        }
+        dirs = dirs
+            .iter()
+            .flat_map(|dir| {
+                let entries = workspace_folder
+                    .join(dir)
+                    .read_dir_utf8()
+                    .into_iter()
+                    .flatten();
+                entries
+                    .filter_map(Result::ok)
+                    .filter(|entry| entry.path().is_dir())
+                    .map(|entry| dir.join(entry.file_name()))
+                    .collect::<Vec<_>>()
+            })
+            .collect();
+    }
+    dirs
+}
+
+impl WorkspaceRoot {
+    pub fn new(name: String, folder: Utf8PathBuf) -> Self {
+        let mut root = Self {
+            name,
+            folder,
+            layout: ProjectLayout::default(),
+            view_dirs: Vec::new(),
+            component_class_dirs: Vec::new(),
+        };
+        root.refresh();
+        root
+    }
+
+    pub fn with_layout(mut self, layout: ProjectLayout) -> Self {
+        self.set_layout(layout);
+        self
+    }
+
+    pub fn layout(&self) -> &ProjectLayout {
+        &self.layout
+    }
+
+    pub fn set_layout(&mut self, layout: ProjectLayout) {
+        self.layout = layout;
+        self.refresh();
+    }
+
+    /// Looks up the directories the layout matches again, returning whether
+    /// they changed, e.g. because a module directory was created
+    pub fn refresh(&mut self) -> bool {
+        let expand = |patterns: &[Utf8PathBuf]| {
+            patterns
+                .iter()
+                .flat_map(|pattern| expand(&self.folder, pattern))
+                .collect::<Vec<_>>()
+        };
+        let view_dirs = expand(&self.layout.view_roots);
+        let component_class_dirs = expand(&self.layout.component_class_roots);
+        if view_dirs == self.view_dirs && component_class_dirs == self.component_class_dirs {
+            return false;
+        }
+        self.view_dirs = view_dirs;
+        self.component_class_dirs = component_class_dirs;
+        true
+    }
+
+    pub fn view_roots(&self) -> impl Iterator<Item = Utf8PathBuf> + '_ {
+        self.view_dirs.iter().map(|dir| self.folder.join(dir))
+    }
+
+    /// Directories of anonymous components, which are inside the view roots
+    pub fn component_views_roots(&self) -> impl Iterator<Item = Utf8PathBuf> + '_ {
+        self.view_roots().map(|root| root.join("components"))
+    }
+
+    pub fn component_class_roots(&self) -> impl Iterator<Item = Utf8PathBuf> + '_ {
+        self.component_class_dirs
+            .iter()
+            .map(|dir| self.folder.join(dir))
+    }
+
+    pub fn namespaces(&self) -> impl Iterator<Item = (&SmolStr, Utf8PathBuf)> + '_ {
+        self.layout
+            .namespaces
+            .iter()
+            .map(|(prefix, dir)| (prefix, self.folder.join(dir)))
+    }
 }
 
 impl Config {
@@ -25,8 +224,56 @@ impl Config {
             .unwrap_or_default()
     }
 
-    pub fn workspace_folder(&self) -> Utf8PathBuf {
-        self.workspace_folder.clone()
+    /// The root a file belongs to, which is the innermost folder holding
+    /// it. Files outside of every folder go with the first one.
+    pub fn root_for(&self, path: &Utf8Path) -> &WorkspaceRoot {
+        static NO_ROOT: LazyLock<WorkspaceRoot> =
+            LazyLock::new(|| WorkspaceRoot::new(String::new(), Utf8PathBuf::new()));
+        self.roots
+            .iter()
+            .filter(|root| path.starts_with(&root.folder))
+            .max_by_key(|root| root.folder.components().count())
+            .or(self.roots.first())
+            .unwrap_or(&NO_ROOT)
+    }
+
+    /// Re-reads the layout of every root from the settings, returning
+    /// whether any of them changed
+    pub fn apply_settings(&mut self, settings: LSPAny) -> bool {
+        let mut changed = false;
+        for root in &mut self.roots {
+            if let Some(layout) = ProjectLayout::from_settings(&settings, root)
+                && layout != root.layout
+            {
+                tracing::info!(root = root.name.as_str(), ?layout, "project layout changed");
+                root.set_layout(layout);
+                changed = true;
+            }
+        }
+        self.settings = settings;
+        changed
+    }
+
+    /// Adds a workspace folder, laid out according to the latest settings
+    pub fn add_root(&mut self, name: String, folder: Utf8PathBuf) {
+        if self.roots.iter().any(|root| root.folder == folder) {
+            return;
+        }
+        let mut root = WorkspaceRoot::new(name, folder);
+        if let Some(layout) = ProjectLayout::from_settings(&self.settings, &root) {
+            root.set_layout(layout);
+        }
+        self.roots.push(root);
+    }
+
+    /// Looks up the directories of every root again, returning whether any
+    /// of them changed
+    pub fn refresh_roots(&mut self) -> bool {
+        let mut changed = false;
+        for root in &mut self.roots {
+            changed |= root.refresh();
+        }
+        changed
     }
 
     pub fn negotiated_encoding(&self) -> PositionEncoding {
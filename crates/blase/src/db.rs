@@ -1,4 +1,5 @@
 use std::{
+    cell::RefCell,
     fmt::Debug,
     sync::{Arc, LazyLock, RwLock},
 };
@@ -14,6 +15,7 @@ use crate::{line_index::LineEndings, lsp, util::FileType};
 
 pub mod def;
 pub mod documentation;
+pub mod source_change;
 pub mod text_edit;
 
 #[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
@@ -52,12 +54,23 @@ impl serde::Serialize for FileRange {
 }
 
 #[salsa::db]
-#[derive(Clone, Default)]
+#[derive(Clone)]
 pub struct RootDatabase {
     storage: salsa::Storage<Self>,
     files: Arc<Files>,
 }
 
+impl Default for RootDatabase {
+    fn default() -> Self {
+        let db = Self {
+            storage: salsa::Storage::default(),
+            files: Arc::default(),
+        };
+        FileSet::new(&db, 0);
+        db
+    }
+}
+
 #[salsa::db]
 impl salsa::Database for RootDatabase {}
 
@@ -72,6 +85,22 @@ pub struct SourceFile {
     pub endings: LineEndings,
 }
 
+/// Changes whenever a file is added or removed. The files themselves are
+/// kept outside of salsa, so queries over all of them, see
+/// [`DocumentDatabase::all_documents`], read this to be recomputed.
+#[salsa::input(singleton)]
+pub struct FileSet {
+    pub revision: u64,
+}
+
+impl FileSet {
+    fn bump(db: &mut dyn Database) {
+        let set = FileSet::get(&*db);
+        let revision = set.revision(&*db);
+        set.set_revision(db).to(revision + 1);
+    }
+}
+
 #[derive(Debug, Default)]
 pub struct Files {
     files: Arc<DashMap<Utf8PathBuf, SourceFile>>,
@@ -117,12 +146,26 @@ impl Files {
                         endings,
                     );
                     vacant.insert(contents);
+                    FileSet::bump(db);
                 } else {
                     tracing::error!(url = path.as_str(), "Unknown filetype");
                 }
             }
         }
     }
+
+    pub fn remove_source_file(&self, db: &mut dyn Database, path: &Utf8Path) -> Option<SourceFile> {
+        let (_, source_file) = self.files.remove(path)?;
+        // Salsa inputs can't be deleted, so empty the file instead to
+        // invalidate whatever was computed from it
+        let contents = "";
+        source_file.set_contents(db).to(Arc::from(contents));
+        source_file
+            .set_line_index(db)
+            .to(Arc::new(LineIndex::new(contents)));
+        FileSet::bump(db);
+        Some(source_file)
+    }
 }
 
 impl RootDatabase {
@@ -135,10 +178,19 @@ impl RootDatabase {
         self.files.len()
     }
 
+    pub fn file_paths(&self) -> impl Iterator<Item = Utf8PathBuf> {
+        self.files.all().map(|(path, _)| path)
+    }
+
     pub fn set_source_file(&mut self, path: Utf8PathBuf, contents: &str) {
         let files = Arc::clone(&self.files);
         files.set_source_file(self, path, contents);
     }
+
+    pub fn remove_source_file(&mut self, path: &Utf8Path) -> Option<SourceFile> {
+        let files = Arc::clone(&self.files);
+        files.remove_source_file(self, path)
+    }
 }
 
 #[salsa::db]
@@ -191,6 +243,8 @@ impl DocumentDatabase for RootDatabase {
     }
 
     fn all_documents(&self) -> Arc<[ParsedDocument]> {
+        // Depend on which files there are, not only on their contents
+        FileSet::get(self).revision(self);
         self.files
             .all()
             .map(|(path, _)| self.parsed_document(&path).unwrap())
@@ -234,6 +288,16 @@ impl ParsedDocument {
         let contents = self.source.contents(db);
         contents.get(node.byte_range())
     }
+
+    /// The syntax errors in the tree. Unlike [`DocumentDatabase::parse_errors`]
+    /// this works for documents that [`parse_document`] doesn't produce, such as
+    /// those from [`parse_embedded_blade`].
+    pub fn parse_errors(&self, db: &dyn DocumentDatabase) -> Vec<ParseError> {
+        let errors = RefCell::new(Vec::new());
+        let accum = |error| errors.borrow_mut().push(error);
+        get_tree_sitter_errors(&accum, self.tree.root_node(), self.source.contents(db));
+        errors.into_inner()
+    }
 }
 
 impl Debug for ParsedDocument {
@@ -405,6 +469,49 @@ pub fn parse_document(db: &dyn DocumentDatabase, source: SourceFile) -> ParsedDo
     }
 }
 
+/// Parses the Blade template written in `range` of a PHP file, e.g. the
+/// heredoc a class component returns from `render`. The rest of the file is
+/// skipped rather than cut out, so nodes keep their offsets in the file.
+pub fn parse_embedded_blade(
+    db: &dyn DocumentDatabase,
+    source: SourceFile,
+    range: line_index::TextRange,
+) -> ParsedDocument {
+    let contents = source.contents(db);
+    let line_index = source.line_index(db);
+    let point = |offset| {
+        let line_col = line_index.line_col(offset);
+        tree_sitter::Point::new(line_col.line as usize, line_col.col as usize)
+    };
+    let included = tree_sitter::Range {
+        start_byte: range.start().into(),
+        end_byte: range.end().into(),
+        start_point: point(range.start()),
+        end_point: point(range.end()),
+    };
+
+    // A parser of its own, since the shared one must keep parsing whole
+    // documents even if this one is cancelled midway
+    let tree = {
+        let mut parser = tree_sitter::Parser::new();
+        parser
+            .set_language(&tree_sitter_blade::LANGUAGE.into())
+            .expect("mismatched version");
+        parser
+            .set_included_ranges(&[included])
+            .expect("A single range is always ordered");
+        parser
+            .parse(contents.as_bytes(), None)
+            .expect("Language has been set")
+    };
+
+    ParsedDocument {
+        source,
+        tree,
+        filetype: FileType::Blade,
+    }
+}
+
 /// Modified from https://github.com/adclz/auto-lsp/blob/d133723bfbd9150c0ec944b4e9f9cf96844dc167/crates/default/src/db/lexer.rs#L30
 ///
 /// Traverse a tree-sitter syntax tree to collect error nodes.
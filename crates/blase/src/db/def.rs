@@ -3,20 +3,30 @@
 
 use std::sync::Arc;
 
-use camino::Utf8Path;
+use ast::NodeExt;
+use camino::{Utf8Path, Utf8PathBuf};
 use convert_case::ccase;
 use itertools::Itertools;
+use line_index::{TextRange, TextSize};
 use smol_str::SmolStr;
-use type_sitter::{HasChild, Node};
+use type_sitter::{HasChild, HasChildren, Node, UntypedNode};
 
 use crate::{
-    config::Config,
-    db::{DocumentDatabase, ParsedDocument, SourceFile},
+    config::WorkspaceRoot,
+    db::{DocumentDatabase, ParsedDocument, SourceFile, def::providers::Registration},
     resolve_path,
     util::FileType,
 };
 
+pub mod attributes;
+pub mod class_component;
+pub mod inheritance;
+pub mod php;
+pub mod providers;
 pub mod queries;
+pub mod slots;
+pub mod stacks;
+pub mod views;
 
 #[salsa::db]
 pub trait DefDatabase: DocumentDatabase {
@@ -56,24 +66,45 @@ impl Name {
     }
 }
 
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub struct ViewName(Name);
 
 impl ViewName {
     pub fn as_str(&self) -> &str {
         self.0.as_str()
     }
+
+    /// Creates a view name from its dotted form, e.g. `partials.header`
+    ///
+    /// Returns None for names that cannot be resolved to a file in the
+    /// workspace, such as namespaced views (`mail::message`).
+    pub fn new(name: &str) -> Option<ViewName> {
+        let valid = |c: char| c.is_alphanumeric() || matches!(c, '-' | '_' | '.' | '/');
+        if name.is_empty() || !name.chars().all(valid) {
+            return None;
+        }
+        let name = name.replace('/', ".");
+        if name.split('.').any(str::is_empty) {
+            return None;
+        }
+        Some(ViewName(Name::new(&name)))
+    }
+
+    pub fn path(&self) -> String {
+        let ViewName(name) = self;
+        name.as_str().replace('.', std::path::MAIN_SEPARATOR_STR)
+    }
+
     pub fn from_document(
         db: &dyn DocumentDatabase,
         doc: &ParsedDocument,
-        ws_path: &Utf8Path,
+        root: &WorkspaceRoot,
     ) -> Option<ViewName> {
-        let path = doc.source.path(db).strip_prefix(ws_path).ok()?;
+        let path = doc.source.path(db);
 
         match doc.filetype {
             FileType::Blade => {
-                let path = path
-                    .strip_prefix(resolve_path::views_dir())
-                    .ok()?
+                let path = relative_to_any(path, root.view_roots())?
                     .as_str()
                     .strip_suffix(".blade.php")?;
                 tracing::debug!(path);
@@ -96,19 +127,39 @@ impl ViewName {
 /// Other attributes (such as HTML attributes) are determined to be available
 /// if the component uses the $attribute variable
 ///
+/// Slots are the variables its template renders as such, see [`slots::Slots`].
 /// If no slots are used, then auto-complete should supply a self-closing tag.
-///
-/// TODO: Determine what slots are available
 #[derive(Clone, PartialEq)]
 pub struct ComponentSignature {
     pub name: ComponentName,
     pub attrs: Option<Arc<[ComponentAttr]>>,
+    /// None if the component's template cannot be found
+    pub slots: Option<slots::Slots>,
+    /// Whether the template renders `$attributes`, or may do so because it
+    /// cannot be found
+    pub forwards_attributes: bool,
 }
 
 #[derive(Debug, Clone, PartialEq)]
 pub struct ComponentAttr {
     pub(crate) name: Name,
     pub(crate) default_value: Option<SmolStr>,
+    /// Whether a usage has to pass the attribute, which is the case unless it
+    /// has a default value or, as a constructor parameter, a nullable type
+    pub(crate) required: bool,
+    /// The type of a constructor parameter as written in the class, taken
+    /// from its `@param` tag if it has one, e.g. `'info'|'error'`
+    pub(crate) ty: Option<SmolStr>,
+    /// The description of a constructor parameter in its `@param` tag
+    pub(crate) docs: Option<SmolStr>,
+}
+
+/// The values an attribute's type allows
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct AttrValues {
+    /// Cases of backed enums and string literals
+    pub strings: Vec<SmolStr>,
+    pub boolean: bool,
 }
 
 macro_rules! walk_children {
@@ -152,7 +203,7 @@ impl ComponentAttr {
                                                             Name::new(name)
                                                         })?;
                                                     let default_value = document.text_for_node(db, array_element.value().ok()?).map(SmolStr::new);
-                                                    Some(ComponentAttr { name, default_value })
+                                                    Some(ComponentAttr { name, default_value, required: false, ty: None, docs: None })
                                                 },
                                                 ArrayElementValueInitializer(array_element) => {
                                                     use ast::blade::{Expression::PrimaryExpression, PrimaryExpression::Literal};
@@ -167,7 +218,7 @@ impl ComponentAttr {
                                                                     _ => None,
                                                                 }?
                                                                 .trim_matches(Self::QUOTES);
-                                                                Some(ComponentAttr { name: Name::new(name), default_value: None })
+                                                                Some(ComponentAttr { name: Name::new(name), default_value: None, required: true, ty: None, docs: None })
                                                             }
                                                             _ => None,
                                                         }},
@@ -211,6 +262,7 @@ impl ComponentAttr {
                                             continue;
                                         }
                                         let method_params = method_decl.parameters().ok()?;
+                                        let tags = php::docblock(db, &document, method_decl.upcast()).map(php::param_tags).unwrap_or_default();
                                         return Some(method_params
                                             .children(&mut cursor)
                                             .filter_map(|param| {
@@ -223,7 +275,11 @@ impl ComponentAttr {
                                                             let text = document.text_for_node(db, val)?;
                                                             Some(SmolStr::new(text))
                                                         });
-                                                        Some(ComponentAttr { name: Name::new(name), default_value })
+                                                        let required = default_value.is_none() && Self::is_required(db, &document, prop_parameter.upcast());
+                                                        let tag = tags.iter().find(|tag| tag.name == name);
+                                                        let ty = Self::param_type(db, &document, prop_parameter.upcast(), tag);
+                                                        let docs = tag.and_then(|tag| tag.description.clone());
+                                                        Some(ComponentAttr { name: Name::new(name), default_value, required, ty, docs })
                                                     },
                                                     SimpleParameter(simple_parameter) => {
                                                         let name = document.text_for_node(db, simple_parameter.name().ok()?)?;
@@ -232,7 +288,11 @@ impl ComponentAttr {
                                                             let text = document.text_for_node(db, val)?;
                                                             Some(SmolStr::new(text))
                                                         });
-                                                        Some(ComponentAttr { name: Name::new(name), default_value })
+                                                        let required = default_value.is_none() && Self::is_required(db, &document, simple_parameter.upcast());
+                                                        let tag = tags.iter().find(|tag| tag.name == name);
+                                                        let ty = Self::param_type(db, &document, simple_parameter.upcast(), tag);
+                                                        let docs = tag.and_then(|tag| tag.description.clone());
+                                                        Some(ComponentAttr { name: Name::new(name), default_value, required, ty, docs })
                                                     },
                                                     _ => None,
                                                 }
@@ -260,19 +320,203 @@ impl ComponentAttr {
     }
 }
 
+impl ComponentAttr {
+    /// How a tag passes the attribute, e.g. `due-date` for `$dueDate`
+    pub fn tag_name(&self) -> String {
+        attributes::tag_attribute(self.name.as_str())
+    }
+
+    /// Whether a tag passing `name` passes this attribute, either as
+    /// declared or in kebab-case
+    pub fn is_passed_as(&self, name: &str) -> bool {
+        attributes::same_attribute(self.name.as_str(), name)
+    }
+
+    /// Whether a plain attribute, e.g. `title="..."`, passes a value of the
+    /// attribute's type. Props are untyped and take anything.
+    pub fn takes_string(&self) -> bool {
+        let Some(ty) = &self.ty else {
+            return true;
+        };
+        ty.trim_start_matches('?')
+            .split('|')
+            .map(str::trim)
+            .all(|ty| {
+                ["string", "null", "mixed"]
+                    .iter()
+                    .any(|string| ty.eq_ignore_ascii_case(string))
+                    || unquote(ty).is_some()
+            })
+    }
+
+    /// The type of the attribute and whether it is required or what it
+    /// defaults to, e.g. `` `?string`, defaults to `null` ``
+    pub fn summary(&self) -> Option<String> {
+        let ty = self.ty.as_ref().map(|ty| format!("`{ty}`"));
+        let default = match &self.default_value {
+            _ if self.required => Some("required".to_owned()),
+            Some(default) => Some(format!("defaults to `{default}`")),
+            None => None,
+        };
+        let summary = ty.into_iter().chain(default).join(", ");
+        (!summary.is_empty()).then_some(summary)
+    }
+
+    /// The summary of the attribute followed by its description
+    pub fn documentation(&self) -> Option<String> {
+        match (self.summary(), &self.docs) {
+            (Some(summary), Some(docs)) => Some(format!("{summary}\n\n{docs}")),
+            (summary, docs) => summary.or_else(|| docs.as_ref().map(SmolStr::to_string)),
+        }
+    }
+
+    /// Whether a usage must pass a constructor parameter without a default,
+    /// which is when the container can't fill it in. Classes other than
+    /// enums are resolved by the container, e.g. `UserRepository $users`,
+    /// and parameters that accept null, e.g. `?string $title`, get null.
+    /// A type that can't be found as a class stays required, since it may
+    /// just as well be an enum the workspace doesn't hold.
+    fn is_required(db: &dyn DocumentDatabase, doc: &ParsedDocument, param: UntypedNode) -> bool {
+        let Some(ty) = php::field(param, "type").and_then(|ty| doc.text_for_node(db, ty)) else {
+            // An untyped parameter is as required as a typed one
+            return true;
+        };
+        if ty.starts_with('?') {
+            return false;
+        }
+        let php = php::PhpFile::for_document(db, doc);
+        !ty.split('|')
+            .map(str::trim)
+            .all(|ty| php::PhpFile::find_class(db, &php.resolve(ty)).is_some())
+    }
+
+    /// The type of a constructor parameter, preferring its `@param` tag,
+    /// which can be narrower than the type PHP allows, e.g. `'info'|'error'`
+    /// over `string`
+    fn param_type(
+        db: &dyn DocumentDatabase,
+        doc: &ParsedDocument,
+        param: UntypedNode,
+        tag: Option<&php::ParamTag>,
+    ) -> Option<SmolStr> {
+        tag.and_then(|tag| tag.ty.clone()).or_else(|| {
+            php::field(param, "type")
+                .and_then(|ty| doc.text_for_node(db, ty))
+                .map(SmolStr::new)
+        })
+    }
+
+    /// The values the attribute's type allows: the cases of a backed enum,
+    /// string literals and booleans. Class names are resolved as written in
+    /// `php`, the file of the class component.
+    pub fn values(&self, db: &dyn DocumentDatabase, php: &php::PhpFile) -> AttrValues {
+        let mut values = AttrValues::default();
+        let Some(ty) = &self.ty else {
+            return values;
+        };
+        for ty in ty.trim_start_matches('?').split('|').map(str::trim) {
+            if ["bool", "true", "false"]
+                .iter()
+                .any(|boolean| ty.eq_ignore_ascii_case(boolean))
+            {
+                values.boolean = true;
+            } else if let Some(string) = unquote(ty) {
+                values.strings.push(SmolStr::new(string));
+            } else if let Some(enumeration) = php::PhpFile::find_enum(db, &php.resolve(ty)) {
+                values.strings.extend(enumeration.values);
+            }
+        }
+        values.strings = values.strings.into_iter().unique().collect();
+        values
+    }
+
+    /// The attribute an `<x-...>` tag passes, as in `title="..."`,
+    /// `:title="..."` or `:$title`, along with the range of the name alone
+    pub fn passed_name<'doc>(
+        db: &'doc dyn DocumentDatabase,
+        doc: &'doc ParsedDocument,
+        attr: ast::blade::Attribute<'doc>,
+    ) -> Option<(&'doc str, TextRange)> {
+        let name = match attr {
+            ast::blade::Attribute::ExpressionAttribute(expression_attribute) => expression_attribute.children(&mut expression_attribute.walk()).filter_map(|ch| {
+                let ch = ch.ok()?;
+                match ch {
+                    ast::blade::anon_unions::ExpressionAttributeName_QuotedExpression::ExpressionAttributeName(attribute_name) => Some(attribute_name.upcast()),
+                    ast::blade::anon_unions::ExpressionAttributeName_QuotedExpression::QuotedExpression(_) => None,
+                }
+            }).last(),
+            ast::blade::Attribute::HtmlAttribute(html_attribute) => html_attribute.children(&mut html_attribute.walk()).filter_map(|ch| {
+                let ch = ch.ok()?;
+                match ch {
+                    ast::blade::anon_unions::AttributeName_AttributeValue_QuotedAttributeValue::AttributeName(attribute_name) => Some(attribute_name.upcast()),
+                    ast::blade::anon_unions::AttributeName_AttributeValue_QuotedAttributeValue::AttributeValue(_) |
+                    ast::blade::anon_unions::AttributeName_AttributeValue_QuotedAttributeValue::QuotedAttributeValue(_) => None,
+                }
+            }).last(),
+            ast::blade::Attribute::ShortAttribute(short_attribute) => short_attribute.variable_name().ok().map(Node::upcast),
+            ast::blade::Attribute::BladeAttribute(_) |
+            ast::blade::Attribute::PhpStatement(_) |
+            ast::blade::Attribute::Comment(_) |
+            ast::blade::Attribute::Conditional(_) |
+            ast::blade::Attribute::Envoy(_) |
+            ast::blade::Attribute::InlineDirective(_) |
+            ast::blade::Attribute::Keyword(_) |
+            ast::blade::Attribute::Livewire(_) |
+            ast::blade::Attribute::Loops(_) |
+            ast::blade::Attribute::Props(_) |
+            ast::blade::Attribute::Switch(_) |
+            ast::blade::Attribute::WireUi(_) => None,
+        }?;
+        let text = doc.text_for_node(db, name)?;
+        let stripped = text
+            .strip_prefix(':') // expression attribute name
+            .or_else(|| text.strip_prefix('$')) // short variable attribute
+            .unwrap_or(text);
+        let end = TextSize::new(name.byte_range().end as u32);
+        let range = TextRange::new(end - TextSize::of(stripped), end);
+        Some((stripped, range))
+    }
+}
+
 #[salsa::tracked]
 impl ComponentSignature {
     pub fn query(db: &dyn DefDatabase, id: ComponentId) -> Arc<ComponentSignature> {
-        let kind = id.kind(db);
-        let file = id.file(db);
-        let filename = file.path(db).file_name().unwrap();
-        let name = ComponentName::from_filename(kind, filename);
+        let name = id.name(db);
         let attrs = ComponentAttr::query(db, id);
+        let template = Component { id }.template(db);
+        let forwards_attributes = template
+            .as_ref()
+            .is_none_or(|template| attributes::renders_attributes(db, template));
+        let members: Vec<Name> = match id.kind(db) {
+            ComponentKind::Class => class_component::class_component(db, id.file(db))
+                .members
+                .iter()
+                .map(|member| Name::new(&member.name))
+                .collect(),
+            ComponentKind::Anon => Vec::new(),
+        };
+        let slots = template.map(|template| {
+            let mut slots = slots::Slots::clone(&slots::Slots::for_document(db, &template));
+            // Echoing a prop or a public member looks just like echoing a
+            // slot. A slot of the same name still fills it, so it stays
+            // among the variables read rather than the named slots.
+            let (variables, named): (Vec<_>, Vec<_>) = slots.named.into_iter().partition(|slot| {
+                attrs.iter().any(|attr| &attr.name == slot) || members.contains(slot)
+            });
+            slots.named = named;
+            slots.read.extend(variables);
+            slots
+        });
         let attrs = match attrs.len() {
             0 => None,
             _ => Some(Arc::from(attrs)),
         };
-        Arc::new(Self { name, attrs })
+        Arc::new(Self {
+            name,
+            attrs,
+            slots,
+            forwards_attributes,
+        })
     }
 }
 
@@ -325,14 +569,12 @@ impl LayoutName {
     pub fn from_document(
         db: &dyn DocumentDatabase,
         doc: &ParsedDocument,
-        ws_path: &Utf8Path,
+        root: &WorkspaceRoot,
     ) -> Option<LayoutName> {
-        let path = doc.source.path(db).strip_prefix(ws_path).ok()?;
+        let path = doc.source.path(db);
         match doc.filetype {
             FileType::Blade => {
-                let path = path
-                    .strip_prefix(resolve_path::views_dir())
-                    .ok()?
+                let path = relative_to_any(path, root.view_roots())?
                     .as_str()
                     .strip_suffix(".blade.php")?;
                 tracing::debug!(path);
@@ -346,9 +588,7 @@ impl LayoutName {
                 }
             }
             FileType::PHP => {
-                let path = path
-                    .strip_prefix(resolve_path::component_class_dir())
-                    .ok()?
+                let path = relative_to_any(path, root.component_class_roots())?
                     .as_str()
                     .strip_suffix("Layout.php")?;
                 tracing::debug!(path);
@@ -394,14 +634,18 @@ impl Layout {
     pub fn from_document(
         db: &dyn DocumentDatabase,
         doc: &ParsedDocument,
-        config: &Config,
+        root: &WorkspaceRoot,
     ) -> Option<Self> {
-        let name = LayoutName::from_document(db, doc, &config.workspace_folder())?;
-        Self::from_name(db, name, config)
+        let name = LayoutName::from_document(db, doc, root)?;
+        Self::from_name(db, name, root)
     }
 
-    pub fn from_name(db: &dyn DocumentDatabase, name: LayoutName, config: &Config) -> Option<Self> {
-        let (class_path, resources_path) = resolve_path::layout_paths(&name, config);
+    pub fn from_name(
+        db: &dyn DocumentDatabase,
+        name: LayoutName,
+        root: &WorkspaceRoot,
+    ) -> Option<Self> {
+        let (class_path, resources_path) = resolve_path::layout_paths(db, &name, root);
         if let Some(class_doc) = db.parsed_document(&class_path) {
             let id = LayoutId::new(db, class_doc.source, LayoutKind::Class, name);
             Some(Self { id })
@@ -417,10 +661,10 @@ impl Layout {
         db: &dyn DocumentDatabase,
         tag: ast::blade::TagName<'_>,
         doc: &ParsedDocument,
-        config: &Config,
+        root: &WorkspaceRoot,
     ) -> Option<Self> {
         let name = LayoutName::new(doc.text_for_node(db, tag)?)?;
-        Self::from_name(db, name, config)
+        Self::from_name(db, name, root)
     }
 }
 
@@ -463,6 +707,12 @@ pub enum Directive {
 
     Each,
 
+    // Template inheritance
+    Extends, Section, Yield,
+
+    // Stacks
+    Stack, Push, PushOnce, PushIf, Prepend, PrependOnce,
+
     Once,
 
     Php, EndPhp,
@@ -512,6 +762,15 @@ impl Directive {
             ast::blade::symbols::Atbreak(_) => Directive::Break,
             ast::blade::symbols::Atcontinue(_) => Directive::Continue,
             ast::blade::symbols::Ateach(_) => Directive::Each,
+            ast::blade::symbols::Atextends(_) => Directive::Extends,
+            ast::blade::symbols::Atsection(_) => Directive::Section,
+            ast::blade::symbols::Atyield(_) => Directive::Yield,
+            ast::blade::symbols::Atstack(_) => Directive::Stack,
+            ast::blade::symbols::Atpush(_) => Directive::Push,
+            ast::blade::symbols::Atpushonce(_) => Directive::PushOnce,
+            ast::blade::symbols::Atpushif(_) => Directive::PushIf,
+            ast::blade::symbols::Atprepend(_) => Directive::Prepend,
+            ast::blade::symbols::Atprependonce(_) => Directive::PrependOnce,
             ast::blade::symbols::Atonce(_) => Directive::Once,
             ast::blade::symbols::Atphp(_) => Directive::Php,
             ast::blade::symbols::Atuse(_) => Directive::Use,
@@ -538,6 +797,36 @@ impl Directive {
         Some(directive)
     }
 
+    /// Determines the directive of a node that holds a directive keyword,
+    /// such as `inline_directive`, `section` or `stack`.
+    pub fn for_directive_node(node: UntypedNode<'_>) -> Option<Self> {
+        let raw = node.raw();
+        let keyword = raw
+            .child_by_field_name("directive")
+            .or_else(|| raw.child_by_field_name("directive_start"))?;
+        Self::from_node(UntypedNode::new(keyword))
+    }
+
+    /// What the string passed at the given argument position of this
+    /// directive refers to
+    pub fn argument_kind(&self, position: usize) -> Option<ArgumentKind> {
+        use Directive::*;
+        let kind = match (self, position) {
+            (Include | IncludeIf | IncludeIsolated | Extends, 0) => ArgumentKind::View,
+            (IncludeWhen | IncludeUnless, 1) => ArgumentKind::View,
+            // @includeFirst takes an array of view names
+            (IncludeFirst, 0) => ArgumentKind::View,
+            // @each('view', $items, 'item', 'empty-view')
+            (Each, 0 | 3) => ArgumentKind::View,
+            (Section | Yield | HasSection | SectionMissing, 0) => ArgumentKind::Section,
+            (Stack | Push | PushOnce | Prepend | PrependOnce, 0) => ArgumentKind::Stack,
+            // @pushIf($condition, 'scripts')
+            (PushIf, 1) => ArgumentKind::Stack,
+            _ => return None,
+        };
+        Some(kind)
+    }
+
     pub fn is_end(&self) -> bool {
         self.lookup().starts_with("end")
     }
@@ -691,6 +980,15 @@ impl Directive {
             Directive::IncludeFirst => "@includefirst",
             Directive::IncludeIsolated => "@includeisolated",
             Directive::Each => "@each",
+            Directive::Extends => "@extends",
+            Directive::Section => "@section",
+            Directive::Yield => "@yield",
+            Directive::Stack => "@stack",
+            Directive::Push => "@push",
+            Directive::PushOnce => "@pushonce",
+            Directive::PushIf => "@pushif",
+            Directive::Prepend => "@prepend",
+            Directive::PrependOnce => "@prependonce",
             Directive::Once => "@once",
             Directive::Php => "@php",
             Directive::EndPhp => "@endphp",
@@ -699,10 +997,111 @@ impl Directive {
     }
 }
 
+/// What a string argument of a directive refers to
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ArgumentKind {
+    View,
+    Section,
+    Stack,
+}
+
+/// Finds the string literal containing `node` if it is passed to a directive
+/// as a name, regardless of whether the literal holds a valid name yet
+pub(crate) fn argument_at(
+    node: UntypedNode<'_>,
+) -> Option<(Directive, ArgumentKind, UntypedNode<'_>)> {
+    let literal = node.ancestors().find(|node| is_string_literal(*node))?;
+    let (parent, directive) = literal
+        .ancestors()
+        .find_map(|node| Some((node, Directive::for_directive_node(node)?)))?;
+    let literal_range = literal.byte_range();
+    let (position, _) = directive_arguments(parent).enumerate().find(|(_, arg)| {
+        let range = arg.byte_range();
+        range.start <= literal_range.start && literal_range.end <= range.end
+    })?;
+    let kind = directive.argument_kind(position)?;
+    Some((directive, kind, literal))
+}
+
+/// Named children of a directive's `parameter` field, i.e. its arguments
+pub(crate) fn directive_arguments<'tree>(
+    node: UntypedNode<'tree>,
+) -> impl Iterator<Item = UntypedNode<'tree>> {
+    let mut cursor = node.raw().walk();
+    node.raw()
+        .children_by_field_name("parameter", &mut cursor)
+        .filter(|node| node.is_named())
+        .map(UntypedNode::new)
+        .collect::<Vec<_>>()
+        .into_iter()
+}
+
+pub(crate) fn is_string_literal(node: UntypedNode<'_>) -> bool {
+    ast::node_is!(node, ast::blade::String | ast::blade::EncapsedString)
+}
+
+/// Strips the quotes from a string literal, rejecting interpolated strings
+pub(crate) fn unquote(text: &str) -> Option<&str> {
+    let inner = text
+        .strip_prefix('\'')
+        .and_then(|s| s.strip_suffix('\''))
+        .or_else(|| text.strip_prefix('"').and_then(|s| s.strip_suffix('"')))?;
+    if inner.contains(['$', '{']) {
+        return None;
+    }
+    Some(inner)
+}
+
+/// The argument naming a section or a stack if it is a plain string literal,
+/// along with the range of that literal
+pub(crate) fn name_argument(
+    db: &dyn DocumentDatabase,
+    doc: &ParsedDocument,
+    node: UntypedNode<'_>,
+) -> Option<(Name, TextRange)> {
+    let directive = Directive::for_directive_node(node)?;
+    let (_, literal) = directive_arguments(node)
+        .enumerate()
+        .find(|(position, _)| directive.argument_kind(*position).is_some())?;
+    if !is_string_literal(literal) {
+        return None;
+    }
+    let name = unquote(doc.text_for_node(db, literal)?)?;
+    if name.is_empty() {
+        return None;
+    }
+    let range = TextRange::new(
+        TextSize::new(literal.start_byte() as u32),
+        TextSize::new(literal.end_byte() as u32),
+    );
+    Some((Name::new(name), range))
+}
+
+/// `path` relative to the first of `roots` it is in
+fn relative_to_any(
+    path: &Utf8Path,
+    mut roots: impl Iterator<Item = Utf8PathBuf>,
+) -> Option<&Utf8Path> {
+    roots.find_map(|root| path.strip_prefix(root).ok())
+}
+
+/// A workspace root as the key of queries over all of its files
+#[salsa::interned(no_lifetime)]
+#[derive(Debug)]
+pub struct RootId {
+    root: WorkspaceRoot,
+}
+
 #[salsa::interned(no_lifetime)]
 pub struct ComponentId {
     file: SourceFile,
     kind: ComponentKind,
+    /// The name the component was found by, which the file alone can't
+    /// tell for namespaced and aliased components
+    name: ComponentName,
+    /// The file of the template the component renders, which a class
+    /// component names in its `render` method or writes there inline
+    template: Option<SourceFile>,
 }
 impl std::fmt::Debug for ComponentId {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
@@ -753,7 +1152,7 @@ pub struct Component {
     pub(crate) id: ComponentId,
 }
 
-#[derive(Clone, PartialEq, Eq)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub struct ComponentName(Name);
 
 impl std::fmt::Display for ComponentName {
@@ -767,46 +1166,92 @@ impl ComponentName {
         &self.0
     }
 
-    pub fn from_filename(kind: ComponentKind, filename: &str) -> Self {
-        let name = match kind {
-            ComponentKind::Class => {
-                Name::new(&ccase!(kebab, filename.strip_suffix(".php").unwrap()))
-            }
-            ComponentKind::Anon => Name::new(filename.strip_suffix(".blade.php").unwrap()),
-        };
-        ComponentName(name)
-    }
-
     pub fn from_document(
         db: &dyn DocumentDatabase,
         doc: &ParsedDocument,
-        ws_path: &Utf8Path,
+        root: &WorkspaceRoot,
     ) -> Option<ComponentName> {
-        let path = doc.source.path(db).strip_prefix(ws_path).ok()?;
-        match doc.filetype {
-            FileType::Blade => {
-                let path = path
-                    .strip_prefix(resolve_path::component_views_dir())
-                    .ok()?
-                    .as_str()
-                    .strip_suffix(".blade.php")?;
-                tracing::debug!(path);
-                let name = path.replace(['\\', '/'], ".");
-                Some(ComponentName(Name::new(&name)))
-            }
-            FileType::PHP => {
-                let path = path
-                    .strip_prefix(resolve_path::component_class_dir())
-                    .ok()?
-                    .as_str()
-                    .strip_suffix(".php")?;
-                tracing::debug!(path);
-                let name = path
-                    .split(['\\', '/'])
-                    .map(|comp| ccase!(kebab, comp))
-                    .join(".");
-                Some(ComponentName(Name::new(&name)))
+        let full_path = doc.source.path(db);
+        let (kind, relative) = match doc.filetype {
+            FileType::Blade => (
+                ComponentKind::Anon,
+                relative_to_any(full_path, root.component_views_roots()),
+            ),
+            FileType::PHP => (
+                ComponentKind::Class,
+                relative_to_any(full_path, root.component_class_roots()),
+            ),
+        };
+        if let Some(path) = relative {
+            tracing::debug!(%path);
+            return Self::from_relative_path(kind, path.as_str());
+        }
+        // Otherwise the component may be registered by a provider or a package
+        providers::registrations(db, root)
+            .iter()
+            .find_map(|registration| match (registration, kind) {
+                (Registration::AnonymousPath { prefix, directory }, ComponentKind::Anon) => {
+                    let path = full_path.strip_prefix(directory).ok()?;
+                    let name = Self::from_relative_path(kind, path.as_str())?;
+                    Some(name.with_namespace(prefix.as_ref()))
+                }
+                (Registration::ClassPath { prefix, directory }, ComponentKind::Class) => {
+                    let path = full_path.strip_prefix(directory).ok()?;
+                    let name = Self::from_relative_path(kind, path.as_str())?;
+                    Some(name.with_namespace(Some(prefix)))
+                }
+                (Registration::ClassNamespace { prefix, namespace }, ComponentKind::Class) => {
+                    let php = php::PhpFile::for_document(db, doc);
+                    let class = php.classes.iter().find_map(|class| {
+                        class.strip_prefix(namespace.as_str())?.strip_prefix('\\')
+                    })?;
+                    Some(Self::from_class_name(class).with_namespace(Some(prefix)))
+                }
+                (Registration::Alias { name, class }, ComponentKind::Class) => {
+                    let php = php::PhpFile::for_document(db, doc);
+                    php.classes
+                        .iter()
+                        .any(|declared| declared.eq_ignore_ascii_case(class))
+                        .then(|| ComponentName(name.clone()))
+                }
+                _ => None,
+            })
+    }
+
+    /// `path` is relative to the component directory of its kind
+    fn from_relative_path(kind: ComponentKind, path: &str) -> Option<ComponentName> {
+        match kind {
+            ComponentKind::Anon => {
+                let path = path.strip_suffix(".blade.php")?;
+                let mut segments = path.split(['\\', '/']).collect::<Vec<_>>();
+                // Both accordion/index.blade.php and accordion/accordion.blade.php
+                // are rendered with <x-accordion>
+                let is_nested = matches!(
+                    segments.as_slice(),
+                    [.., parent, last] if *last == "index" || last == parent
+                );
+                if is_nested {
+                    segments.pop();
+                }
+                Some(ComponentName(Name::new(&segments.join("."))))
             }
+            ComponentKind::Class => Some(Self::from_class_name(path.strip_suffix(".php")?)),
+        }
+    }
+
+    /// `Forms\TextInput` is rendered with `<x-forms.text-input>`
+    fn from_class_name(class: &str) -> ComponentName {
+        let name = class
+            .split(['\\', '/'])
+            .map(|comp| ccase!(kebab, comp))
+            .join(".");
+        ComponentName(Name::new(&name))
+    }
+
+    fn with_namespace(self, namespace: Option<&Name>) -> ComponentName {
+        match namespace {
+            Some(namespace) => ComponentName(Name::new(&format!("{namespace}::{}", self.0))),
+            None => self,
         }
     }
 
@@ -816,15 +1261,27 @@ impl ComponentName {
         }
         let name = name.strip_prefix("x-")?;
         // slots are not components
-        if name == "slot" {
+        if name == "slot" || name.starts_with("slot:") {
             return None;
         }
         Some(ComponentName(Name::new(name)))
     }
 
+    /// The prefix of a namespaced component, e.g. `courier` in `<x-courier::alert>`
+    pub fn namespace(&self) -> Option<Name> {
+        let (namespace, _) = self.0.as_str().split_once("::")?;
+        Some(Name::new(namespace))
+    }
+
+    /// The name without its namespace
+    pub fn local_name(&self) -> &str {
+        let name = self.0.as_str();
+        name.split_once("::").map_or(name, |(_, local)| local)
+    }
+
     pub fn path(&self) -> String {
-        let ComponentName(name) = self;
-        name.as_str().replace('.', std::path::MAIN_SEPARATOR_STR)
+        self.local_name()
+            .replace('.', std::path::MAIN_SEPARATOR_STR)
     }
 
     pub fn tag_name(&self) -> String {
@@ -852,13 +1309,49 @@ impl Component {
         self.signature(db).attrs.clone()
     }
 
-    pub fn for_name(db: &dyn DefDatabase, name: &ComponentName, config: &Config) -> Option<Self> {
-        let (class_path, resources_path) = resolve_path::component_paths(name, config);
+    pub fn is_anonymous(&self, db: &dyn DefDatabase) -> bool {
+        matches!(self.id.kind(db), ComponentKind::Anon)
+    }
+
+    /// The template the component renders, which is the component itself
+    /// for anonymous components
+    pub fn template(&self, db: &dyn DefDatabase) -> Option<ParsedDocument> {
+        class_component::blade_document(db, self.id.template(db)?)
+    }
+
+    pub fn for_name(
+        db: &dyn DefDatabase,
+        name: &ComponentName,
+        root: &WorkspaceRoot,
+    ) -> Option<Self> {
+        let (class_path, resources_path) = resolve_path::component_paths(db, name, root);
         if let Some(class_doc) = db.parsed_document(&class_path) {
-            let id = ComponentId::new(db, class_doc.source, ComponentKind::Class);
+            let template = match resolve_path::component_template_at(
+                db,
+                &class_path,
+                resources_path.clone(),
+                root,
+            ) {
+                Some(path) => db.parsed_document(&path).map(|template| template.source),
+                // The class writes its template inline
+                None => Some(class_doc.source),
+            };
+            let id = ComponentId::new(
+                db,
+                class_doc.source,
+                ComponentKind::Class,
+                name.clone(),
+                template,
+            );
             Some(Self { id })
         } else if let Some(res_doc) = db.parsed_document(&resources_path) {
-            let id = ComponentId::new(db, res_doc.source, ComponentKind::Anon);
+            let id = ComponentId::new(
+                db,
+                res_doc.source,
+                ComponentKind::Anon,
+                name.clone(),
+                Some(res_doc.source),
+            );
             Some(Self { id })
         } else {
             None
@@ -869,7 +1362,7 @@ impl Component {
         db: &dyn DefDatabase,
         attr: ast::blade::Attribute,
         doc: &ParsedDocument,
-        config: &Config,
+        root: &WorkspaceRoot,
     ) -> Option<Self> {
         let tag = attr.parent()?;
         let tag_name = ast::match_node!(tag, {
@@ -884,7 +1377,7 @@ impl Component {
                 },
                 _ => None,
         })?;
-        let component = Self::for_tagname(db, tag_name, doc, config)?;
+        let component = Self::for_tagname(db, tag_name, doc, root)?;
         Some(component)
     }
 
@@ -892,18 +1385,18 @@ impl Component {
         db: &dyn DefDatabase,
         tag: ast::blade::TagName<'_>,
         doc: &ParsedDocument,
-        config: &Config,
+        root: &WorkspaceRoot,
     ) -> Option<Self> {
         let name = ComponentName::new(doc.text_for_node(db, tag)?)?;
-        Self::for_name(db, &name, config)
+        Self::for_name(db, &name, root)
     }
 
     pub fn for_document(
         db: &dyn DefDatabase,
         doc: &ParsedDocument,
-        config: &Config,
+        root: &WorkspaceRoot,
     ) -> Option<Self> {
-        let name = ComponentName::from_document(db, doc, &config.workspace_folder())?;
-        Self::for_name(db, &name, config)
+        let name = ComponentName::from_document(db, doc, root)?;
+        Self::for_name(db, &name, root)
     }
 }
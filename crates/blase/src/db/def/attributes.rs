@@ -0,0 +1,439 @@
+//! Component attributes on both ends: where a component declares them and
+//! where `<x-...>` tags pass them, e.g. `<x-alert title="..." :$type/>`
+
+use std::sync::Arc;
+
+use ast::NodeExt;
+use camino::Utf8PathBuf;
+use line_index::{TextRange, TextSize};
+use type_sitter::{Node, UntypedNode};
+
+use crate::{
+    db::{
+        DocumentDatabase, ParsedDocument, SourceFile,
+        def::{
+            Component, ComponentAttr, ComponentName, DefDatabase, Name, class_component,
+            php::field, slots::SlotFill,
+        },
+    },
+    util::FileType,
+};
+
+/// How a tag passes an attribute
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PassedAttrKind {
+    /// `title="..."`
+    Html,
+    /// `:title="..."`
+    Expression,
+    /// `:$title`, which passes the variable of the same name
+    Short,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PassedAttr {
+    pub name: Name,
+    pub kind: PassedAttrKind,
+    /// Range of the name alone
+    pub range: TextRange,
+    /// Range of the whole attribute, including its value
+    pub attr_range: TextRange,
+}
+
+/// A start or self-closing `<x-...>` tag along with the attributes it passes
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ComponentUsage {
+    /// The tag name without its `x-` prefix
+    pub name: Name,
+    pub tag_range: TextRange,
+    pub attrs: Vec<PassedAttr>,
+    /// Whether the tag passes on an attribute bag, either with
+    /// `:attributes="..."` or `{{ $attributes }}`, which may hold anything
+    pub forwards_attributes: bool,
+    /// The named slots filled in between the tags, e.g. `title` in
+    /// `<x-slot:title>`
+    pub slots: Vec<Name>,
+}
+
+/// Every component tag in a single template
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ComponentUsages {
+    pub usages: Vec<ComponentUsage>,
+}
+
+#[salsa::tracked]
+pub fn component_usages(db: &dyn DocumentDatabase, file: SourceFile) -> Arc<ComponentUsages> {
+    let mut usages = ComponentUsages::default();
+    let Some(doc) = &class_component::blade_document(db, file) else {
+        return Arc::new(usages);
+    };
+    let elements = doc
+        .root_node()
+        .descendants()
+        .filter_map(|node| node.downcast::<ast::blade::Element>().ok());
+    for element in elements {
+        let (Some(tag), Some(tag_name)) = (element.tag(), element.tag_name()) else {
+            continue;
+        };
+        let Some(name) = doc
+            .text_for_node(db, tag_name)
+            .and_then(|text| text.strip_prefix("x-"))
+        else {
+            continue;
+        };
+        // slots are not components
+        if name == "slot" || name.starts_with("slot:") {
+            continue;
+        }
+        let mut cursor = tag.walk();
+        let attrs = tag
+            .untyped_children(&mut cursor)
+            .filter_map(|child| child.downcast::<ast::blade::Attribute>().ok())
+            .filter_map(|attr| {
+                let kind = match attr {
+                    ast::blade::Attribute::HtmlAttribute(_) => PassedAttrKind::Html,
+                    ast::blade::Attribute::ExpressionAttribute(_) => PassedAttrKind::Expression,
+                    ast::blade::Attribute::ShortAttribute(_) => PassedAttrKind::Short,
+                    _ => return None,
+                };
+                let (name, range) = ComponentAttr::passed_name(db, doc, attr)?;
+                Some(PassedAttr {
+                    name: Name::new(name),
+                    kind,
+                    range,
+                    attr_range: node_range(attr),
+                })
+            })
+            .collect::<Vec<_>>();
+        let forwards_attributes = attrs.iter().any(|attr| attr.name.as_str() == "attributes")
+            || tag
+                .untyped_children(&mut cursor)
+                .filter(|child| ast::node_is!(*child, ast::blade::Escaped | ast::blade::Unescaped))
+                .flat_map(|echo| echo.descendants())
+                .filter(|node| node.is::<ast::blade::VariableName>())
+                .any(|variable| doc.text_for_node(db, variable) == Some("$attributes"));
+        let slots = element
+            .upcast()
+            .descendants()
+            .skip(1)
+            .filter_map(|node| node.downcast::<ast::blade::Element>().ok())
+            .filter(|slot| {
+                SlotFill::filled_element(db, doc, slot.upcast())
+                    .is_some_and(|outer| outer.raw() == element.raw())
+            })
+            .filter_map(|slot| SlotFill::for_element(db, doc, slot))
+            .map(|fill| fill.name)
+            .collect();
+        usages.usages.push(ComponentUsage {
+            name: Name::new(name),
+            tag_range: node_range(tag_name),
+            attrs,
+            forwards_attributes,
+            slots,
+        });
+    }
+    Arc::new(usages)
+}
+
+impl ComponentUsages {
+    pub fn for_document(db: &dyn DocumentDatabase, doc: &ParsedDocument) -> Arc<ComponentUsages> {
+        component_usages(db, doc.source)
+    }
+
+    /// The usages of a component in this template
+    pub fn of<'a>(&'a self, name: &'a ComponentName) -> impl Iterator<Item = &'a ComponentUsage> {
+        self.usages
+            .iter()
+            .filter(move |usage| &usage.name == name.inner())
+    }
+}
+
+impl ComponentUsage {
+    /// Every usage of a component throughout the workspace, grouped by file,
+    /// including those in the inline templates of class components
+    pub fn all(
+        db: &dyn DocumentDatabase,
+        name: &ComponentName,
+    ) -> Vec<(Utf8PathBuf, Vec<ComponentUsage>)> {
+        db.all_documents()
+            .iter()
+            .filter_map(|doc| {
+                let usages = ComponentUsages::for_document(db, doc)
+                    .of(name)
+                    .cloned()
+                    .collect::<Vec<_>>();
+                if usages.is_empty() {
+                    None
+                } else {
+                    Some((doc.source.path(db).to_owned(), usages))
+                }
+            })
+            .collect()
+    }
+}
+
+/// Whether a tag passing `passed` passes the attribute `declared`. Laravel
+/// passes `due-date="..."` to a `$dueDate` parameter or prop, so the name
+/// may also be written as [`tag_attribute`] spells it, but not the other
+/// way round: `due_date` is not `$dueDate`.
+pub fn same_attribute(declared: &str, passed: &str) -> bool {
+    declared == passed || tag_attribute(declared) == passed
+}
+
+/// The spelling of an attribute in a tag, e.g. `due-date` for `$dueDate`.
+/// Like Laravel's `Str::kebab`, only uppercase letters start a new word, so
+/// `$item_id` stays `item_id`.
+pub fn tag_attribute(name: &str) -> String {
+    let mut tag = String::with_capacity(name.len());
+    for (i, c) in name.chars().enumerate() {
+        if i > 0 && c.is_uppercase() {
+            tag.push('-');
+        }
+        tag.extend(c.to_lowercase());
+    }
+    tag
+}
+
+/// Whether a component template renders `$attributes`, which takes every
+/// attribute the component doesn't declare
+pub fn renders_attributes(db: &dyn DocumentDatabase, doc: &ParsedDocument) -> bool {
+    doc.root_node()
+        .descendants()
+        .filter(|node| node.is::<ast::blade::VariableName>())
+        .any(|node| doc.text_for_node(db, node) == Some("$attributes"))
+}
+
+/// Where a component declares one of its attributes, either as a key of
+/// `@props` or as a parameter of the constructor
+#[derive(Debug, Clone, Copy)]
+pub struct AttrDeclaration<'doc> {
+    pub name: &'doc str,
+    /// The name alone, without its quotes or `$`
+    pub range: TextRange,
+    /// The constructor declaring the attribute, if it is a class component
+    pub constructor: Option<UntypedNode<'doc>>,
+    /// Whether the template reads the attribute as a variable, which it does
+    /// unless the attribute is a constructor parameter that isn't promoted
+    pub is_variable: bool,
+}
+
+/// A place where a component reads one of its attributes
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AttrRead {
+    pub path: Utf8PathBuf,
+    pub range: TextRange,
+    pub kind: AttrReadKind,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AttrReadKind {
+    /// `$title`
+    Variable,
+    /// `title` in `$this->title`
+    Property,
+    /// `$title` in `:$title`, which passes it on to another component
+    ShortAttribute { attr_range: TextRange },
+}
+
+impl<'doc> AttrDeclaration<'doc> {
+    /// Every attribute the component defined in `doc` declares
+    pub fn all(db: &'doc dyn DocumentDatabase, doc: &'doc ParsedDocument) -> Vec<Self> {
+        doc.root_node()
+            .descendants()
+            .filter_map(|node| match doc.filetype {
+                FileType::Blade
+                    if ast::node_is!(node, ast::blade::String | ast::blade::EncapsedString) =>
+                {
+                    Self::in_props(db, doc, node)
+                }
+                FileType::PHP if node.is::<ast::php::VariableName>() => {
+                    Self::in_constructor(db, doc, node)
+                }
+                _ => None,
+            })
+            .collect()
+    }
+
+    pub fn at(
+        db: &'doc dyn DocumentDatabase,
+        doc: &'doc ParsedDocument,
+        offset: TextSize,
+    ) -> Option<Self> {
+        Self::all(db, doc)
+            .into_iter()
+            .find(|decl| decl.range.contains_inclusive(offset))
+    }
+
+    pub fn named(
+        db: &'doc dyn DocumentDatabase,
+        doc: &'doc ParsedDocument,
+        name: &str,
+    ) -> Option<Self> {
+        Self::all(db, doc)
+            .into_iter()
+            .find(|decl| decl.name == name)
+    }
+
+    /// A string in `@props(['title' => null])` or `@props(['title'])`
+    fn in_props(
+        db: &'doc dyn DocumentDatabase,
+        doc: &'doc ParsedDocument,
+        string: UntypedNode<'doc>,
+    ) -> Option<Self> {
+        let parent = string.parent()?;
+        let is_key = parent.is::<ast::blade::ArrayElementKeyValueInitializer>()
+            && field(parent, "key").is_some_and(|key| key.byte_range() == string.byte_range());
+        let is_prop = (is_key || parent.is::<ast::blade::ArrayElementValueInitializer>())
+            && parent
+                .ancestors()
+                .any(|node| node.is::<ast::blade::Props>());
+        if !is_prop {
+            return None;
+        }
+        let text = doc.text_for_node(db, string)?;
+        let name = text.strip_prefix(['\'', '"'])?.strip_suffix(['\'', '"'])?;
+        let start = TextSize::new(string.start_byte() as u32) + TextSize::of('\'');
+        Some(AttrDeclaration {
+            name,
+            range: TextRange::at(start, TextSize::of(name)),
+            constructor: None,
+            is_variable: true,
+        })
+    }
+
+    /// A parameter of `__construct`, which the template can read if it is
+    /// promoted to a property
+    fn in_constructor(
+        db: &'doc dyn DocumentDatabase,
+        doc: &'doc ParsedDocument,
+        variable: UntypedNode<'doc>,
+    ) -> Option<Self> {
+        let param = variable.parent()?;
+        let promoted = param.is::<ast::php::PropertyPromotionParameter>();
+        let is_param = (promoted || param.is::<ast::php::SimpleParameter>())
+            && field(param, "name").is_some_and(|name| name.byte_range() == variable.byte_range());
+        if !is_param {
+            return None;
+        }
+        let constructor = param
+            .ancestors()
+            .find(|node| node.is::<ast::php::MethodDeclaration>())?;
+        if doc.text_for_node(db, field(constructor, "name")?)? != "__construct" {
+            return None;
+        }
+        let name = doc.text_for_node(db, variable)?.strip_prefix('$')?;
+        let end = TextSize::new(variable.end_byte() as u32);
+        Some(AttrDeclaration {
+            name,
+            range: TextRange::new(end - TextSize::of(name), end),
+            constructor: Some(constructor),
+            is_variable: promoted,
+        })
+    }
+
+    /// The template that reads the attribute as a variable
+    pub fn template(&self, db: &dyn DefDatabase, component: Component) -> Option<ParsedDocument> {
+        if !self.is_variable {
+            return None;
+        }
+        component.template(db)
+    }
+
+    /// Where `component`, declared in `doc`, reads the attribute: in the
+    /// body of the constructor, through `$this` and in the template
+    pub fn reads(
+        &self,
+        db: &dyn DefDatabase,
+        doc: &ParsedDocument,
+        component: Component,
+    ) -> Vec<AttrRead> {
+        let variable = format!("${}", self.name);
+        let mut reads = Vec::new();
+        let path = doc.source.path(db);
+        if let Some(constructor) = self.constructor {
+            let variables = constructor
+                .descendants()
+                .filter(|node| node.is::<ast::php::VariableName>())
+                .filter(|node| !node_range(*node).contains_range(self.range))
+                .filter(|node| doc.text_for_node(db, *node) == Some(variable.as_str()));
+            reads.extend(variables.map(|node| AttrRead {
+                path: path.to_owned(),
+                range: node_range(node),
+                kind: AttrReadKind::Variable,
+            }));
+        }
+        let class = self
+            .constructor
+            .filter(|_| self.is_variable)
+            .and_then(|constructor| {
+                constructor
+                    .ancestors()
+                    .find(|node| node.is::<ast::php::ClassDeclaration>())
+            });
+        if let Some(class) = class {
+            let properties = class
+                .descendants()
+                .filter(|node| node.is::<ast::php::MemberAccessExpression>())
+                .filter(|node| {
+                    field(*node, "object").and_then(|object| doc.text_for_node(db, object))
+                        == Some("$this")
+                })
+                .filter_map(|node| field(node, "name"))
+                .filter(|name| doc.text_for_node(db, *name) == Some(self.name));
+            reads.extend(properties.map(|node| AttrRead {
+                path: path.to_owned(),
+                range: node_range(node),
+                kind: AttrReadKind::Property,
+            }));
+        }
+        if let Some(template) = self.template(db, component) {
+            let path = template.source.path(db);
+            let variables = template
+                .root_node()
+                .descendants()
+                .filter(|node| node.is::<ast::blade::VariableName>())
+                .filter(|node| template.text_for_node(db, *node) == Some(variable.as_str()));
+            reads.extend(variables.map(|node| {
+                let kind = match node.parent() {
+                    Some(parent) if parent.is::<ast::blade::ShortAttribute>() => {
+                        AttrReadKind::ShortAttribute {
+                            attr_range: node_range(parent),
+                        }
+                    }
+                    _ => AttrReadKind::Variable,
+                };
+                AttrRead {
+                    path: path.to_owned(),
+                    range: node_range(node),
+                    kind,
+                }
+            }));
+        }
+        reads
+    }
+}
+
+/// Where the component or layout defined in `doc` declares itself: the name
+/// of its class or its `@props` directive. Templates without `@props` start
+/// at the top of the file.
+pub fn definition_range(doc: &ParsedDocument) -> TextRange {
+    let root = doc.root_node();
+    let node = match doc.filetype {
+        FileType::PHP => root
+            .descendants()
+            .find(|node| node.is::<ast::php::ClassDeclaration>())
+            .and_then(|class| field(class, "name")),
+        FileType::Blade => root
+            .descendants()
+            .find(|node| node.is::<ast::blade::Props>()),
+    };
+    node.map(node_range).unwrap_or_default()
+}
+
+pub(crate) fn node_range<'tree>(node: impl Node<'tree>) -> TextRange {
+    let range = node.byte_range();
+    TextRange::new(
+        TextSize::new(range.start as u32),
+        TextSize::new(range.end as u32),
+    )
+}
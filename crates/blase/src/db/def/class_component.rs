@@ -0,0 +1,339 @@
+//! What a class component exposes to its template: the template its
+//! `render` method returns and the public properties and methods the
+//! template reads as variables, e.g. `{{ $title }}` and `{{ $shout() }}`.
+//! A template written inline as a heredoc is parsed as Blade in place.
+
+use std::{collections::HashMap, sync::Arc};
+
+use ast::NodeExt;
+use camino::Utf8PathBuf;
+use line_index::{TextRange, TextSize};
+use smol_str::SmolStr;
+use type_sitter::{Node, UntypedNode};
+
+use crate::{
+    config::WorkspaceRoot,
+    db::{
+        DocumentDatabase, ParsedDocument, SourceFile,
+        def::{
+            ComponentName, RootId, ViewName,
+            attributes::node_range,
+            php::{field, string_value},
+        },
+        parse_document, parse_embedded_blade,
+    },
+    resolve_path,
+    util::FileType,
+};
+
+/// Methods of `Illuminate\View\Component` that are not exposed to the view
+const IGNORED_METHODS: &[&str] = &[
+    "data",
+    "render",
+    "resolve",
+    "resolveView",
+    "shouldRender",
+    "view",
+    "withName",
+    "withAttributes",
+    "flushCache",
+    "forgetFactory",
+    "forgetComponentsResolver",
+    "resolveComponentsUsing",
+];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MemberKind {
+    Property,
+    Method,
+}
+
+/// A public property or method of a class component
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Member {
+    /// The name without its `$`
+    pub name: SmolStr,
+    pub kind: MemberKind,
+    /// The name in the class, without its `$`
+    pub range: TextRange,
+    /// The type of a property or the return type of a method
+    pub ty: Option<SmolStr>,
+}
+
+/// What the `render` method of a class component returns
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Render {
+    /// A view by name, e.g. `components.alert` in `return view('components.alert');`
+    /// or `return View::make('components.alert');`
+    View(ViewName),
+    /// A template written in the class as a heredoc or nowdoc string
+    Inline {
+        /// The contents of the string, without its delimiters
+        range: TextRange,
+    },
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ClassComponent {
+    /// None if `render` is missing or returns something else, such as a
+    /// view whose name is computed
+    pub render: Option<Render>,
+    pub members: Vec<Member>,
+}
+
+#[salsa::tracked]
+pub fn class_component(db: &dyn DocumentDatabase, file: SourceFile) -> Arc<ClassComponent> {
+    let doc = &parse_document(db, file);
+    let mut component = ClassComponent::default();
+    if doc.filetype != FileType::PHP {
+        return Arc::new(component);
+    }
+    let Some(body) = doc
+        .root_node()
+        .descendants()
+        .find(|node| node.is::<ast::php::ClassDeclaration>())
+        .and_then(|class| field(class, "body"))
+    else {
+        return Arc::new(component);
+    };
+    let mut cursor = body.walk();
+    for item in body.untyped_children(&mut cursor) {
+        if !is_public(db, doc, item) {
+            continue;
+        }
+        if item.is::<ast::php::PropertyDeclaration>() {
+            let mut cursor = item.walk();
+            // Static properties belong to the class rather than the instance
+            if item
+                .untyped_children(&mut cursor)
+                .any(|child| child.is::<ast::php::StaticModifier>())
+            {
+                continue;
+            }
+            let ty = text_of(db, doc, field(item, "type"));
+            let properties = item
+                .untyped_children(&mut cursor)
+                .filter(|child| child.is::<ast::php::PropertyElement>())
+                .filter_map(|property| field(property, "name"))
+                .filter_map(|name| property(db, doc, name, ty.clone()));
+            component.members.extend(properties);
+        } else if item.is::<ast::php::MethodDeclaration>()
+            && let Some(name) = field(item, "name")
+            && let Some(text) = doc.text_for_node(db, name)
+        {
+            if text == "__construct" {
+                // Promoted parameters are properties too
+                let properties = field(item, "parameters")
+                    .into_iter()
+                    .flat_map(|params| params.descendants())
+                    .filter(|param| param.is::<ast::php::PropertyPromotionParameter>())
+                    .filter(|param| {
+                        text_of(db, doc, field(*param, "visibility")).as_deref() == Some("public")
+                    })
+                    .filter_map(|param| {
+                        let ty = text_of(db, doc, field(param, "type"));
+                        property(db, doc, field(param, "name")?, ty)
+                    });
+                component.members.extend(properties);
+            }
+            if text == "render" {
+                component.render = render(db, doc, item);
+            }
+            if text.starts_with("__") || IGNORED_METHODS.contains(&text) {
+                continue;
+            }
+            component.members.push(Member {
+                name: SmolStr::new(text),
+                kind: MemberKind::Method,
+                range: node_range(name),
+                ty: text_of(db, doc, field(item, "return_type")),
+            });
+        }
+    }
+    Arc::new(component)
+}
+
+/// The template a class component returns from `render` as a heredoc or
+/// nowdoc string, parsed as Blade
+#[salsa::tracked]
+pub fn inline_template(db: &dyn DocumentDatabase, file: SourceFile) -> Option<ParsedDocument> {
+    match class_component(db, file).render {
+        Some(Render::Inline { range }) => Some(parse_embedded_blade(db, file, range)),
+        _ => None,
+    }
+}
+
+/// The class components of a workspace keyed by the template each renders,
+/// so that a template finds its class without resolving every template
+#[salsa::tracked]
+fn class_components_by_template(
+    db: &dyn DocumentDatabase,
+    root: RootId,
+) -> Arc<HashMap<Utf8PathBuf, SourceFile>> {
+    let root = &root.root(db);
+    let mut classes = HashMap::new();
+    for doc in db.all_documents().iter() {
+        if doc.filetype != FileType::PHP {
+            continue;
+        }
+        let template = ComponentName::from_document(db, doc, root)
+            .and_then(|name| resolve_path::component_template(db, &name, root));
+        if let Some(template) = template {
+            classes.entry(template).or_insert(doc.source);
+        }
+    }
+    Arc::new(classes)
+}
+
+/// The Blade in `file`: the whole file if it is a template, or the template
+/// it writes inline if it is a class component
+pub fn blade_document(db: &dyn DocumentDatabase, file: SourceFile) -> Option<ParsedDocument> {
+    match file.file_type(db) {
+        FileType::Blade => Some(parse_document(db, file)),
+        FileType::PHP => inline_template(db, file),
+    }
+}
+
+impl ClassComponent {
+    pub fn for_document(db: &dyn DocumentDatabase, doc: &ParsedDocument) -> Arc<ClassComponent> {
+        class_component(db, doc.source)
+    }
+
+    /// The inline template of the class component declared in `doc`, as long
+    /// as `doc` is in a directory of class components
+    pub fn inline_template(
+        db: &dyn DocumentDatabase,
+        doc: &ParsedDocument,
+        root: &WorkspaceRoot,
+    ) -> Option<ParsedDocument> {
+        if doc.filetype != FileType::PHP {
+            return None;
+        }
+        ComponentName::from_document(db, doc, root)?;
+        inline_template(db, doc.source)
+    }
+
+    /// The document to analyse at `offset` in `doc`: the inline template
+    /// when `offset` is inside it, or else `doc` itself
+    pub fn document_at(
+        db: &dyn DocumentDatabase,
+        doc: ParsedDocument,
+        offset: TextSize,
+        root: &WorkspaceRoot,
+    ) -> ParsedDocument {
+        let in_template = match &Self::for_document(db, &doc).render {
+            Some(Render::Inline { range }) => range.contains_inclusive(offset),
+            _ => false,
+        };
+        if !in_template {
+            return doc;
+        }
+        Self::inline_template(db, &doc, root).unwrap_or(doc)
+    }
+
+    /// Finds the class component that renders `template`, along with the
+    /// document declaring it
+    pub fn for_template(
+        db: &dyn DocumentDatabase,
+        template: &ParsedDocument,
+        root: &WorkspaceRoot,
+    ) -> Option<(ParsedDocument, Arc<ClassComponent>)> {
+        if template.filetype != FileType::Blade {
+            return None;
+        }
+        // An inline template is written in the class itself
+        let class = class_component(db, template.source);
+        if let Some(Render::Inline { .. }) = class.render {
+            let doc = db.parsed_document(template.source.path(db))?;
+            return Some((doc, class));
+        }
+        let classes = class_components_by_template(db, RootId::new(db, root.clone()));
+        let file = *classes.get(template.source.path(db))?;
+        Some((parse_document(db, file), class_component(db, file)))
+    }
+
+    pub fn member(&self, name: &str) -> Option<&Member> {
+        self.members.iter().find(|member| member.name == name)
+    }
+}
+
+/// What the first `return` of a `render` method that can be followed
+/// returns: `view('...')`, `View::make('...')` or a heredoc
+fn render(
+    db: &dyn DocumentDatabase,
+    doc: &ParsedDocument,
+    method: UntypedNode<'_>,
+) -> Option<Render> {
+    method
+        .descendants()
+        .filter(|node| node.is::<ast::php::ReturnStatement>())
+        .find_map(|statement| {
+            let mut cursor = statement.walk();
+            let value = statement
+                .untyped_children(&mut cursor)
+                .find(|child| child.raw().is_named())?;
+            if ast::node_is!(value, ast::php::Heredoc | ast::php::Nowdoc) {
+                // An empty heredoc has no body
+                let range = match field(value, "value") {
+                    Some(body) => node_range(body),
+                    None => TextRange::empty(node_range(field(value, "end_tag")?).start()),
+                };
+                return Some(Render::Inline { range });
+            }
+            let (function, expected) = if value.is::<ast::php::FunctionCallExpression>() {
+                (field(value, "function")?, "view")
+            } else if value.is::<ast::php::ScopedCallExpression>() {
+                let scope = doc.text_for_node(db, field(value, "scope")?)?;
+                // `View::make` or the fully qualified facade
+                if scope.rsplit('\\').next() != Some("View") {
+                    return None;
+                }
+                (field(value, "name")?, "make")
+            } else {
+                return None;
+            };
+            if doc.text_for_node(db, function)? != expected {
+                return None;
+            }
+            let argument = field(value, "arguments")?
+                .descendants()
+                .find(|node| node.is::<ast::php::Argument>())?;
+            let mut cursor = argument.walk();
+            let name = argument.untyped_children(&mut cursor).next()?;
+            ViewName::new(&string_value(db, doc, name)?).map(Render::View)
+        })
+}
+
+/// A property named by `variable`, e.g. `$title`
+fn property(
+    db: &dyn DocumentDatabase,
+    doc: &ParsedDocument,
+    variable: UntypedNode<'_>,
+    ty: Option<SmolStr>,
+) -> Option<Member> {
+    let name = doc.text_for_node(db, variable)?.strip_prefix('$')?;
+    let end = node_range(variable).end();
+    Some(Member {
+        name: SmolStr::new(name),
+        kind: MemberKind::Property,
+        range: TextRange::new(end - TextSize::of(name), end),
+        ty,
+    })
+}
+
+/// Whether a property or method is public, which it is unless it says
+/// otherwise
+fn is_public(db: &dyn DocumentDatabase, doc: &ParsedDocument, item: UntypedNode<'_>) -> bool {
+    let mut cursor = item.walk();
+    item.untyped_children(&mut cursor)
+        .filter(|child| child.is::<ast::php::VisibilityModifier>())
+        .all(|modifier| doc.text_for_node(db, modifier) == Some("public"))
+}
+
+fn text_of(
+    db: &dyn DocumentDatabase,
+    doc: &ParsedDocument,
+    node: Option<UntypedNode<'_>>,
+) -> Option<SmolStr> {
+    doc.text_for_node(db, node?).map(SmolStr::new)
+}
@@ -0,0 +1,174 @@
+//! Template inheritance through `@extends`, `@section` and `@yield`
+
+use std::{collections::HashSet, sync::Arc};
+
+use ast::NodeExt;
+use itertools::Itertools;
+use line_index::{TextRange, TextSize};
+use type_sitter::{Node, UntypedNode};
+
+use crate::{
+    config::WorkspaceRoot,
+    db::{
+        DocumentDatabase, ParsedDocument, SourceFile,
+        def::{
+            Directive, Name, class_component, name_argument,
+            views::{self, ViewReference},
+        },
+    },
+    util::FileType,
+};
+
+/// A section name along with where it is written
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SectionSite {
+    pub name: Name,
+    pub range: TextRange,
+}
+
+/// What a single template contributes to an inheritance chain
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Template {
+    /// The view passed to `@extends`
+    pub extends: Option<ViewReference>,
+    /// Sections filled in with `@section`
+    pub sections: Vec<SectionSite>,
+    /// Places where sections are rendered, either with `@yield` or
+    /// with a `@section` that ends in `@show`
+    pub yields: Vec<SectionSite>,
+    /// `@parent` placeholders, named after the section they are in
+    pub parents: Vec<SectionSite>,
+}
+
+#[salsa::tracked]
+pub fn template(db: &dyn DocumentDatabase, file: SourceFile) -> Arc<Template> {
+    let mut template = Template::default();
+    let Some(doc) = &class_component::blade_document(db, file) else {
+        return Arc::new(template);
+    };
+    for node in doc.root_node().descendants() {
+        if node.is::<ast::blade::symbols::Atparent>() {
+            let section = node
+                .ancestors()
+                .find(|node| node.is::<ast::blade::Section>())
+                .and_then(|section| name_argument(db, doc, section));
+            if let Some((name, _)) = section {
+                let range = TextRange::new(
+                    TextSize::new(node.start_byte() as u32),
+                    TextSize::new(node.end_byte() as u32),
+                );
+                template.parents.push(SectionSite { name, range });
+            }
+            continue;
+        }
+        match Directive::for_directive_node(node) {
+            Some(Directive::Extends) if template.extends.is_none() => {
+                template.extends = ViewReference::for_directive(db, doc, node).pop();
+            }
+            Some(Directive::Section) => {
+                let Some((name, range)) = name_argument(db, doc, node) else {
+                    continue;
+                };
+                let site = SectionSite { name, range };
+                if ends_with_show(db, doc, node) {
+                    template.yields.push(site.clone());
+                }
+                template.sections.push(site);
+            }
+            Some(Directive::Yield) => {
+                if let Some((name, range)) = name_argument(db, doc, node) {
+                    template.yields.push(SectionSite { name, range });
+                }
+            }
+            _ => {}
+        }
+    }
+    Arc::new(template)
+}
+
+fn ends_with_show(
+    db: &dyn DocumentDatabase,
+    doc: &ParsedDocument,
+    section: UntypedNode<'_>,
+) -> bool {
+    let mut cursor = section.walk();
+    section
+        .untyped_children(&mut cursor)
+        .filter(|child| child.is::<ast::blade::DirectiveEnd>())
+        .any(|end| doc.text_for_node(db, end) == Some("@show"))
+}
+
+impl Template {
+    pub fn for_document(db: &dyn DocumentDatabase, doc: &ParsedDocument) -> Arc<Template> {
+        template(db, doc.source)
+    }
+
+    /// The views this document extends, nearest parent first
+    pub fn ancestors(
+        db: &dyn DocumentDatabase,
+        doc: &ParsedDocument,
+        root: &WorkspaceRoot,
+    ) -> Vec<ParsedDocument> {
+        let mut seen = HashSet::from([doc.source]);
+        let mut ancestors = Vec::new();
+        let mut current = doc.clone();
+        while let Some(extends) = &Self::for_document(db, &current).extends
+            && let Some(parent) = db.parsed_document(&extends.path(db, root))
+            && seen.insert(parent.source)
+        {
+            ancestors.push(parent.clone());
+            current = parent;
+        }
+        ancestors
+    }
+
+    /// Whether every view in the chain of `@extends` exists in the workspace
+    pub fn is_chain_complete(
+        db: &dyn DocumentDatabase,
+        doc: &ParsedDocument,
+        root: &WorkspaceRoot,
+    ) -> bool {
+        let ancestors = Self::ancestors(db, doc, root);
+        let topmost = ancestors.last().unwrap_or(doc);
+        Self::for_document(db, topmost).extends.is_none()
+    }
+
+    /// Views that directly or indirectly extend this document
+    pub fn descendants(
+        db: &dyn DocumentDatabase,
+        doc: &ParsedDocument,
+        root: &WorkspaceRoot,
+    ) -> Vec<ParsedDocument> {
+        db.all_documents()
+            .iter()
+            .filter(|other| other.filetype == FileType::Blade && other.source != doc.source)
+            .filter(|other| {
+                Self::ancestors(db, other, root)
+                    .iter()
+                    .any(|ancestor| ancestor.source == doc.source)
+            })
+            .cloned()
+            .sorted_by(|a, b| a.source.path(db).cmp(b.source.path(db)))
+            .collect()
+    }
+
+    /// Sections rendered by this document, including the ones yielded
+    /// from views it includes
+    pub fn rendered_sections(
+        db: &dyn DocumentDatabase,
+        doc: &ParsedDocument,
+        root: &WorkspaceRoot,
+    ) -> Vec<(ParsedDocument, SectionSite)> {
+        views::with_included_views(db, doc, root)
+            .into_iter()
+            .flat_map(|doc| {
+                let template = Self::for_document(db, &doc);
+                template
+                    .yields
+                    .iter()
+                    .map(|site| (doc.clone(), site.clone()))
+                    .collect::<Vec<_>>()
+            })
+            .collect()
+    }
+}
@@ -0,0 +1,297 @@
+//! Facts about the PHP files that templates depend on, such as the
+//! classes and enums they declare and the names they import
+
+use std::{collections::HashMap, sync::Arc};
+
+use ast::NodeExt;
+use smol_str::SmolStr;
+use type_sitter::{Node, UntypedNode};
+
+use crate::{
+    db::{DocumentDatabase, FileSet, ParsedDocument, SourceFile, parse_document},
+    util::FileType,
+};
+
+/// The namespace, imports, classes and enums of a single PHP file
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct PhpFile {
+    pub namespace: Option<SmolStr>,
+    /// Imported class names keyed by their alias, which defaults to the
+    /// last segment of the name
+    pub imports: Vec<(SmolStr, SmolStr)>,
+    /// Fully qualified names of the declared classes
+    pub classes: Vec<SmolStr>,
+    pub enums: Vec<PhpEnum>,
+}
+
+/// An enum declared in a PHP file, e.g. `enum AlertType: string`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PhpEnum {
+    /// Fully qualified name
+    pub name: SmolStr,
+    /// The values of its cases, e.g. `info` for `case Info = 'info';`.
+    /// Empty unless the enum is backed.
+    pub values: Vec<SmolStr>,
+}
+
+/// A `@param` tag of a docblock, e.g. `@param 'info'|'error' $type The kind of alert`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct ParamTag {
+    /// The parameter name without its `$`
+    pub name: SmolStr,
+    pub ty: Option<SmolStr>,
+    /// The text after the parameter name
+    pub description: Option<SmolStr>,
+}
+
+#[salsa::tracked]
+pub fn php_file(db: &dyn DocumentDatabase, file: SourceFile) -> Arc<PhpFile> {
+    let doc = &parse_document(db, file);
+    let mut php = PhpFile::default();
+    if doc.filetype != FileType::PHP {
+        return Arc::new(php);
+    }
+    for node in doc.root_node().descendants() {
+        if node.is::<ast::php::NamespaceDefinition>() && php.namespace.is_none() {
+            php.namespace = field(node, "name")
+                .and_then(|name| doc.text_for_node(db, name))
+                .map(SmolStr::new);
+        } else if node.is::<ast::php::NamespaceUseDeclaration>() {
+            // `use function` and `use const` do not import classes
+            if field(node, "type").is_some() {
+                continue;
+            }
+            // The common prefix of a group, as in `use App\{Foo, Bar}`
+            let prefix = field(node, "body").and_then(|_| {
+                let mut cursor = node.walk();
+                node.untyped_children(&mut cursor)
+                    .find(|child| child.is::<ast::php::NamespaceName>())
+                    .and_then(|prefix| doc.text_for_node(db, prefix))
+            });
+            let clauses = node
+                .descendants()
+                .filter(|node| node.is::<ast::php::NamespaceUseClause>());
+            for clause in clauses {
+                let Some(text) = doc.text_for_node(db, clause) else {
+                    continue;
+                };
+                let (name, alias) = match text.split_whitespace().collect::<Vec<_>>()[..] {
+                    [name, keyword, alias] if keyword.eq_ignore_ascii_case("as") => (name, alias),
+                    [name] => (name, name.rsplit('\\').next().unwrap_or(name)),
+                    _ => continue,
+                };
+                let name = name.trim_start_matches('\\');
+                let name = match prefix {
+                    Some(prefix) => SmolStr::new(format!("{prefix}\\{name}")),
+                    None => SmolStr::new(name),
+                };
+                php.imports.push((SmolStr::new(alias), name));
+            }
+        } else if node.is::<ast::php::ClassDeclaration>()
+            && let Some(name) = field(node, "name").and_then(|name| doc.text_for_node(db, name))
+        {
+            let class = php.qualify(name);
+            php.classes.push(class);
+        } else if node.is::<ast::php::EnumDeclaration>()
+            && let Some(name) = field(node, "name").and_then(|name| doc.text_for_node(db, name))
+        {
+            let values = node
+                .descendants()
+                .filter(|node| node.is::<ast::php::EnumCase>())
+                .filter_map(|case| field(case, "value"))
+                .filter_map(|value| match string_value(db, doc, value) {
+                    Some(string) => Some(SmolStr::new(string)),
+                    None => doc.text_for_node(db, value).map(SmolStr::new),
+                })
+                .collect();
+            php.enums.push(PhpEnum {
+                name: php.qualify(name),
+                values,
+            });
+        }
+    }
+    Arc::new(php)
+}
+
+/// The classes and enums declared throughout the workspace, keyed by
+/// their fully qualified names in lowercase, as PHP ignores their case
+#[derive(Debug, Default, PartialEq, Eq)]
+pub struct Declarations {
+    classes: HashMap<SmolStr, SourceFile>,
+    enums: HashMap<SmolStr, PhpEnum>,
+}
+
+/// Keyed on the set of files, which is what [`DocumentDatabase::all_documents`]
+/// changes with besides their contents
+#[salsa::tracked]
+pub fn declarations(db: &dyn DocumentDatabase, _files: FileSet) -> Arc<Declarations> {
+    let mut declarations = Declarations::default();
+    let docs = db.all_documents();
+    for doc in docs.iter().filter(|doc| doc.filetype == FileType::PHP) {
+        let php = PhpFile::for_document(db, doc);
+        for class in &php.classes {
+            declarations
+                .classes
+                .entry(class.to_lowercase().into())
+                .or_insert(doc.source);
+        }
+        for enumeration in &php.enums {
+            declarations
+                .enums
+                .entry(enumeration.name.to_lowercase().into())
+                .or_insert_with(|| enumeration.clone());
+        }
+    }
+    Arc::new(declarations)
+}
+
+impl PhpFile {
+    pub fn for_document(db: &dyn DocumentDatabase, doc: &ParsedDocument) -> Arc<PhpFile> {
+        php_file(db, doc.source)
+    }
+
+    /// Resolves a class name as written in this file to its fully
+    /// qualified name, going through the imports
+    pub fn resolve(&self, name: &str) -> SmolStr {
+        if let Some(name) = name.strip_prefix('\\') {
+            return SmolStr::new(name);
+        }
+        let (first, rest) = match name.split_once('\\') {
+            Some((first, rest)) => (first, Some(rest)),
+            None => (name, None),
+        };
+        let import = self
+            .imports
+            .iter()
+            .find(|(alias, _)| alias.eq_ignore_ascii_case(first));
+        match (import, rest) {
+            (Some((_, class)), Some(rest)) => SmolStr::new(format!("{class}\\{rest}")),
+            (Some((_, class)), None) => class.clone(),
+            (None, _) => self.qualify(name),
+        }
+    }
+
+    fn qualify(&self, name: &str) -> SmolStr {
+        match &self.namespace {
+            Some(namespace) => SmolStr::new(format!("{namespace}\\{name}")),
+            None => SmolStr::new(name),
+        }
+    }
+
+    /// Finds the document declaring a class by its fully qualified name
+    pub fn find_class(db: &dyn DocumentDatabase, class: &str) -> Option<ParsedDocument> {
+        let class = class.trim_start_matches('\\').to_lowercase();
+        let file = *declarations(db, FileSet::get(db))
+            .classes
+            .get(class.as_str())?;
+        Some(parse_document(db, file))
+    }
+
+    /// Finds an enum by its fully qualified name
+    pub fn find_enum(db: &dyn DocumentDatabase, name: &str) -> Option<PhpEnum> {
+        let name = name.trim_start_matches('\\').to_lowercase();
+        declarations(db, FileSet::get(db))
+            .enums
+            .get(name.as_str())
+            .cloned()
+    }
+}
+
+pub(crate) fn field<'tree>(node: UntypedNode<'tree>, name: &str) -> Option<UntypedNode<'tree>> {
+    node.raw().child_by_field_name(name).map(UntypedNode::new)
+}
+
+/// The value of a plain string literal, e.g. `App\View` for `'App\\View'`.
+/// Returns None if the string interpolates variables.
+pub(crate) fn string_value(
+    db: &dyn DocumentDatabase,
+    doc: &ParsedDocument,
+    node: UntypedNode<'_>,
+) -> Option<String> {
+    if !ast::node_is!(node, ast::php::String | ast::php::EncapsedString) {
+        return None;
+    }
+    let text = doc.text_for_node(db, node)?;
+    let quote = text.chars().next()?;
+    let inner = text.strip_prefix(quote)?.strip_suffix(quote)?;
+    if quote == '"' && inner.contains(['$', '{']) {
+        return None;
+    }
+    let mut value = String::with_capacity(inner.len());
+    let mut chars = inner.chars().peekable();
+    while let Some(ch) = chars.next() {
+        match chars.peek() {
+            Some(&next) if ch == '\\' && (next == '\\' || next == quote) => {
+                value.push(next);
+                chars.next();
+            }
+            _ => value.push(ch),
+        }
+    }
+    Some(value)
+}
+
+/// The fully qualified name in `Foo::class` or in a class name string
+pub(crate) fn class_value(
+    db: &dyn DocumentDatabase,
+    doc: &ParsedDocument,
+    node: UntypedNode<'_>,
+) -> Option<SmolStr> {
+    if node.is::<ast::php::ClassConstantAccessExpression>() {
+        let mut cursor = node.walk();
+        let mut children = node
+            .untyped_children(&mut cursor)
+            .filter(|child| child.raw().is_named());
+        let (class, constant) = (children.next()?, children.next()?);
+        if doc.text_for_node(db, constant)? != "class" {
+            return None;
+        }
+        let name = doc.text_for_node(db, class)?;
+        return Some(PhpFile::for_document(db, doc).resolve(name));
+    }
+    let name = string_value(db, doc, node)?;
+    Some(SmolStr::new(name.trim_start_matches('\\')))
+}
+
+/// The docblock right before a declaration, e.g. the `/** ... */` above
+/// `public function __construct(...)`
+pub(crate) fn docblock<'doc>(
+    db: &'doc dyn DocumentDatabase,
+    doc: &'doc ParsedDocument,
+    node: UntypedNode<'_>,
+) -> Option<&'doc str> {
+    let comment = UntypedNode::new(node.raw().prev_named_sibling()?);
+    if !comment.is::<ast::php::Comment>() {
+        return None;
+    }
+    doc.text_for_node(db, comment)
+        .filter(|text| text.starts_with("/**"))
+}
+
+/// The `@param` tags of a docblock
+pub(crate) fn param_tags(docblock: &str) -> Vec<ParamTag> {
+    docblock
+        .lines()
+        .filter_map(|line| {
+            let line = line
+                .trim()
+                .trim_start_matches("/**")
+                .trim_start_matches('*');
+            let tag = line.trim_start().strip_prefix("@param")?;
+            // The type may contain spaces, as in `'info' | 'error'`, so
+            // everything before the variable is taken as the type
+            let (ty, rest) = tag.split_once('$')?;
+            let end = rest
+                .find(|ch: char| ch.is_whitespace() || ch == '*')
+                .unwrap_or(rest.len());
+            let (name, description) = rest.split_at(end);
+            let ty = ty.trim().trim_end_matches(['&', '.']).trim();
+            let description = description.trim().trim_end_matches("*/").trim_end();
+            Some(ParamTag {
+                name: SmolStr::new(name),
+                ty: (!ty.is_empty()).then(|| SmolStr::new(ty)),
+                description: (!description.is_empty()).then(|| SmolStr::new(description)),
+            })
+        })
+        .collect()
+}
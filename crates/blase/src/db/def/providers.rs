@@ -0,0 +1,419 @@
+//! Components registered by service providers, such as namespaces added
+//! with `Blade::componentNamespace`, and the components of packages
+
+use std::sync::Arc;
+
+use ast::NodeExt;
+use camino::{Utf8Component, Utf8Path, Utf8PathBuf};
+use convert_case::ccase;
+use itertools::Itertools;
+use smol_str::SmolStr;
+use type_sitter::{Node, UntypedNode};
+
+use crate::{
+    config::WorkspaceRoot,
+    db::{
+        DocumentDatabase, ParsedDocument, SourceFile,
+        def::{
+            Name, RootId,
+            php::{class_value, field, string_value},
+        },
+        parse_document,
+    },
+    resolve_path,
+    util::FileType,
+};
+
+/// Where components are found besides the application's own directories
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum Registration {
+    /// Anonymous components in a directory, added with
+    /// `Blade::anonymousComponentPath` or `Blade::anonymousComponentNamespace`.
+    /// Without a prefix, they are used as `<x-name>` like the application's own.
+    AnonymousPath {
+        prefix: Option<Name>,
+        directory: Utf8PathBuf,
+    },
+    /// Class components in a PHP namespace, added with `Blade::componentNamespace`
+    ClassNamespace { prefix: Name, namespace: SmolStr },
+    /// Class components in a directory, e.g. a package's `src/View/Components`
+    ClassPath {
+        prefix: Name,
+        directory: Utf8PathBuf,
+    },
+    /// A class component under a name of its own, added with `Blade::component`,
+    /// `Blade::components` or `loadViewComponentsAs`
+    Alias { name: Name, class: SmolStr },
+}
+
+impl Registration {
+    pub fn prefix(&self) -> Option<&Name> {
+        match self {
+            Self::AnonymousPath { prefix, .. } => prefix.as_ref(),
+            Self::ClassNamespace { prefix, .. } | Self::ClassPath { prefix, .. } => Some(prefix),
+            Self::Alias { .. } => None,
+        }
+    }
+}
+
+/// Registrations made by a single service provider of a workspace
+#[salsa::tracked]
+pub fn provider_registrations(
+    db: &dyn DocumentDatabase,
+    file: SourceFile,
+    root: RootId,
+) -> Arc<Vec<Registration>> {
+    let doc = &parse_document(db, file);
+    let root = &root.root(db);
+    let mut registrations = Vec::new();
+    if doc.filetype != FileType::PHP {
+        return Arc::new(registrations);
+    }
+    for node in doc.root_node().descendants() {
+        let Some(method) = field(node, "name").and_then(|name| doc.text_for_node(db, name)) else {
+            continue;
+        };
+        let Some(args) = field(node, "arguments") else {
+            continue;
+        };
+        if node.is::<ast::php::ScopedCallExpression>() {
+            let is_blade = field(node, "scope")
+                .and_then(|scope| doc.text_for_node(db, scope))
+                .is_some_and(|scope| scope.rsplit('\\').next() == Some("Blade"));
+            if !is_blade {
+                continue;
+            }
+            match method {
+                "component" => registrations.extend(component_alias(db, doc, args)),
+                "components" => {
+                    let Some(components) = argument(db, doc, args, 0, "components") else {
+                        continue;
+                    };
+                    let prefix = argument(db, doc, args, 1, "prefix")
+                        .and_then(|arg| string_value(db, doc, arg));
+                    // Classes are either keys mapped to their aliases or plain values
+                    let aliases = array_elements(components).filter_map(|(key, value)| {
+                        let (class, alias) = match key {
+                            Some(class) => (class, Some(value)),
+                            None => (value, None),
+                        };
+                        alias_registration(db, doc, class, alias, prefix.as_deref())
+                    });
+                    registrations.extend(aliases);
+                }
+                _ => registrations.extend(blade_registration(db, doc, root, method, args)),
+            }
+        } else if node.is::<ast::php::MemberCallExpression>() && method == "loadViewComponentsAs" {
+            let Some(prefix) =
+                argument(db, doc, args, 0, "prefix").and_then(|arg| string_value(db, doc, arg))
+            else {
+                continue;
+            };
+            let Some(components) = argument(db, doc, args, 1, "components") else {
+                continue;
+            };
+            // Aliases are either keys mapped to their classes or left out
+            let aliases = array_elements(components).filter_map(|(alias, class)| {
+                let alias = alias.filter(|alias| string_value(db, doc, *alias).is_some());
+                alias_registration(db, doc, class, alias, Some(&prefix))
+            });
+            registrations.extend(aliases);
+        }
+    }
+    Arc::new(registrations)
+}
+
+/// `Blade::component('alert', Alert::class)`, which also takes the class
+/// first and then an optional alias
+fn component_alias(
+    db: &dyn DocumentDatabase,
+    doc: &ParsedDocument,
+    args: UntypedNode<'_>,
+) -> Option<Registration> {
+    let first = argument(db, doc, args, 0, "class")?;
+    let second = argument(db, doc, args, 1, "alias");
+    let (class, alias) = match second {
+        Some(second) if is_class_name(db, doc, second) => (second, Some(first)),
+        _ => (first, second),
+    };
+    let prefix = argument(db, doc, args, 2, "prefix").and_then(|arg| string_value(db, doc, arg));
+    alias_registration(db, doc, class, alias, prefix.as_deref())
+}
+
+fn is_class_name(db: &dyn DocumentDatabase, doc: &ParsedDocument, node: UntypedNode<'_>) -> bool {
+    node.is::<ast::php::ClassConstantAccessExpression>()
+        || string_value(db, doc, node).is_some_and(|value| value.contains('\\'))
+}
+
+/// Without an alias, Laravel names `App\View\Components\Forms\Input` as
+/// `forms:input` and any other class after its kebab-cased basename
+fn alias_registration(
+    db: &dyn DocumentDatabase,
+    doc: &ParsedDocument,
+    class: UntypedNode<'_>,
+    alias: Option<UntypedNode<'_>>,
+    prefix: Option<&str>,
+) -> Option<Registration> {
+    let class = class_value(db, doc, class)?;
+    let alias = match alias {
+        Some(alias) => string_value(db, doc, alias)?,
+        None => match class.split_once("\\View\\Components\\") {
+            Some((_, rest)) => rest
+                .split('\\')
+                .map(|segment| ccase!(kebab, segment))
+                .join(":"),
+            None => ccase!(kebab, class.rsplit('\\').next()?),
+        },
+    };
+    let name = match prefix.filter(|prefix| !prefix.is_empty()) {
+        Some(prefix) => format!("{prefix}-{alias}"),
+        None => alias,
+    };
+    Some(Registration::Alias {
+        name: Name::new(&name),
+        class,
+    })
+}
+
+/// Registrations made with a static call on the `Blade` facade
+fn blade_registration(
+    db: &dyn DocumentDatabase,
+    doc: &ParsedDocument,
+    root: &WorkspaceRoot,
+    method: &str,
+    args: UntypedNode<'_>,
+) -> Vec<Registration> {
+    let string_argument = |position, name| {
+        argument(db, doc, args, position, name).and_then(|arg| string_value(db, doc, arg))
+    };
+    match method {
+        "componentNamespace" => {
+            let (Some(namespace), Some(prefix)) = (
+                string_argument(0, "namespace"),
+                string_argument(1, "prefix"),
+            ) else {
+                return Vec::new();
+            };
+            vec![Registration::ClassNamespace {
+                prefix: Name::new(&prefix),
+                namespace: SmolStr::new(namespace.trim_matches('\\')),
+            }]
+        }
+        "anonymousComponentPath" => {
+            let Some(directory) =
+                argument(db, doc, args, 0, "path").and_then(|arg| path_value(db, doc, root, arg))
+            else {
+                return Vec::new();
+            };
+            let prefix = string_argument(1, "prefix");
+            vec![Registration::AnonymousPath {
+                prefix: prefix.as_deref().map(Name::new),
+                directory,
+            }]
+        }
+        "anonymousComponentNamespace" => {
+            let Some(directory) = string_argument(0, "directory") else {
+                return Vec::new();
+            };
+            // The prefix defaults to the directory itself
+            let prefix =
+                Name::new(&string_argument(1, "prefix").unwrap_or_else(|| directory.clone()));
+            // Like any other view, the directory may be in any of the view roots
+            let directory = directory.replace('.', std::path::MAIN_SEPARATOR_STR);
+            root.view_roots()
+                .map(|view_root| Registration::AnonymousPath {
+                    prefix: Some(prefix.clone()),
+                    directory: view_root.join(&directory),
+                })
+                .collect()
+        }
+        _ => Vec::new(),
+    }
+}
+
+/// Every registration that applies to a workspace: the namespaces in its
+/// configuration, the ones made by its service providers, and then the
+/// components of its packages
+pub fn registrations(db: &dyn DocumentDatabase, root: &WorkspaceRoot) -> Vec<Registration> {
+    root_registrations(db, RootId::new(db, root.clone())).to_vec()
+}
+
+#[salsa::tracked]
+fn root_registrations(db: &dyn DocumentDatabase, root_id: RootId) -> Arc<Vec<Registration>> {
+    let root = root_id.root(db);
+    let ws_path = &root.folder;
+    let providers_dir = ws_path.join(resolve_path::providers_dir());
+    let vendor_dir = ws_path.join(resolve_path::vendor_dir());
+    // A configured directory may hold either kind of component
+    let mut registrations = root
+        .namespaces()
+        .flat_map(|(prefix, directory)| {
+            let prefix = Name::new(prefix);
+            [
+                Registration::AnonymousPath {
+                    prefix: Some(prefix.clone()),
+                    directory: directory.clone(),
+                },
+                Registration::ClassPath { prefix, directory },
+            ]
+        })
+        .collect::<Vec<_>>();
+    let docs = db.all_documents();
+    let providers = docs
+        .iter()
+        .filter(|doc| doc.filetype == FileType::PHP)
+        .filter(|doc| doc.source.path(db).starts_with(&providers_dir))
+        .sorted_by(|a, b| a.source.path(db).cmp(b.source.path(db)))
+        .flat_map(|doc| provider_registrations(db, doc.source, root_id).to_vec());
+    registrations.extend(providers);
+    let packages = docs
+        .iter()
+        .filter_map(|doc| package_registration(doc.source.path(db), &vendor_dir))
+        .unique()
+        .sorted_by_key(|registration| registration.prefix().map(|prefix| prefix.to_string()));
+    registrations.extend(packages);
+    Arc::new(registrations)
+}
+
+/// Packages are expected to use their own name as the prefix, e.g.
+/// `<x-courier::alert>` for `vendor/acme/courier`
+fn package_registration(path: &Utf8Path, vendor_dir: &Utf8Path) -> Option<Registration> {
+    let mut components = path.strip_prefix(vendor_dir).ok()?.components();
+    let (vendor, package) = (components.next()?, components.next()?);
+    let package_dir = vendor_dir.join(vendor).join(package);
+    let rest = components.as_path();
+    let prefix = Name::new(package.as_str());
+    if rest.starts_with(resolve_path::component_views_dir()) {
+        let directory = package_dir.join(resolve_path::component_views_dir());
+        Some(Registration::AnonymousPath {
+            prefix: Some(prefix),
+            directory,
+        })
+    } else if rest.starts_with(resolve_path::package_component_class_dir()) {
+        let directory = package_dir.join(resolve_path::package_component_class_dir());
+        Some(Registration::ClassPath { prefix, directory })
+    } else {
+        None
+    }
+}
+
+/// The expression passed for a parameter, either by position or by name
+fn argument<'tree>(
+    db: &dyn DocumentDatabase,
+    doc: &ParsedDocument,
+    args: UntypedNode<'tree>,
+    position: usize,
+    name: &str,
+) -> Option<UntypedNode<'tree>> {
+    let mut cursor = args.walk();
+    let args = args
+        .untyped_children(&mut cursor)
+        .filter(|arg| arg.is::<ast::php::Argument>())
+        .collect::<Vec<_>>();
+    let named = args.iter().find(|arg| {
+        field(**arg, "name").and_then(|name| doc.text_for_node(db, name)) == Some(name)
+    });
+    let arg = match named {
+        Some(arg) => *arg,
+        None => *args
+            .iter()
+            .filter(|arg| field(**arg, "name").is_none())
+            .nth(position)?,
+    };
+    let mut cursor = arg.walk();
+    arg.untyped_children(&mut cursor)
+        .filter(|child| child.raw().is_named())
+        .last()
+}
+
+/// Keys and values of an array literal, e.g. `['alert' => Alert::class]`
+fn array_elements<'tree>(
+    array: UntypedNode<'tree>,
+) -> impl Iterator<Item = (Option<UntypedNode<'tree>>, UntypedNode<'tree>)> {
+    let mut cursor = array.walk();
+    let elements = array
+        .untyped_children(&mut cursor)
+        .filter(|child| child.is::<ast::php::ArrayElementInitializer>())
+        .collect::<Vec<_>>();
+    elements.into_iter().filter_map(|element| {
+        let mut cursor = element.walk();
+        let children = element
+            .untyped_children(&mut cursor)
+            .filter(|child| child.raw().is_named())
+            .collect::<Vec<_>>();
+        match children[..] {
+            [key, value] => Some((Some(key), value)),
+            [value] => Some((None, value)),
+            _ => None,
+        }
+    })
+}
+
+/// Evaluates the path expressions commonly given to providers, such as
+/// `__DIR__.'/../../resources/views/components'` or `resource_path('views/admin')`
+fn path_value(
+    db: &dyn DocumentDatabase,
+    doc: &ParsedDocument,
+    root: &WorkspaceRoot,
+    node: UntypedNode<'_>,
+) -> Option<Utf8PathBuf> {
+    let path = Utf8PathBuf::from(path_string(db, doc, root, node)?);
+    // Resolve `..` without touching the file system
+    let mut normalized = Utf8PathBuf::new();
+    for component in path.components() {
+        match component {
+            Utf8Component::ParentDir => {
+                normalized.pop();
+            }
+            Utf8Component::CurDir => {}
+            component => normalized.push(component),
+        }
+    }
+    Some(normalized)
+}
+
+fn path_string(
+    db: &dyn DocumentDatabase,
+    doc: &ParsedDocument,
+    root: &WorkspaceRoot,
+    node: UntypedNode<'_>,
+) -> Option<String> {
+    if let Some(value) = string_value(db, doc, node) {
+        return Some(value);
+    }
+    let provider = doc.source.path(db);
+    if node.is::<ast::php::Name>() && doc.text_for_node(db, node)? == "__DIR__" {
+        return Some(provider.parent()?.to_string());
+    }
+    if node.is::<ast::php::ParenthesizedExpression>() {
+        let mut cursor = node.walk();
+        let inner = node
+            .untyped_children(&mut cursor)
+            .find(|child| child.raw().is_named())?;
+        return path_string(db, doc, root, inner);
+    }
+    if node.is::<ast::php::BinaryExpression>() {
+        let operator = field(node, "operator").and_then(|op| doc.text_for_node(db, op))?;
+        if operator != "." {
+            return None;
+        }
+        let left = path_string(db, doc, root, field(node, "left")?)?;
+        let right = path_string(db, doc, root, field(node, "right")?)?;
+        return Some(left + &right);
+    }
+    if node.is::<ast::php::FunctionCallExpression>() {
+        let function = field(node, "function").and_then(|f| doc.text_for_node(db, f))?;
+        let base = match function.trim_start_matches('\\') {
+            "base_path" => root.folder.clone(),
+            "app_path" => root.folder.join("app"),
+            "resource_path" => root.folder.join("resources"),
+            _ => return None,
+        };
+        let path = match argument(db, doc, field(node, "arguments")?, 0, "path") {
+            Some(arg) => base.join(path_string(db, doc, root, arg)?),
+            None => base,
+        };
+        return Some(path.to_string());
+    }
+    None
+}
@@ -0,0 +1,258 @@
+//! Slots rendered by component templates, e.g. `{{ $slot }}`
+
+use std::sync::Arc;
+
+use ast::NodeExt;
+use line_index::{TextRange, TextSize};
+use type_sitter::{Node, UntypedNode};
+
+use crate::{
+    config::WorkspaceRoot,
+    db::{
+        DocumentDatabase, ParsedDocument, SourceFile,
+        def::{Component, DefDatabase, Name, attributes, class_component},
+    },
+};
+
+/// Variables Laravel passes to every component template, which are never slots
+const RESERVED_VARIABLES: [&str; 5] = ["attributes", "component", "errors", "loop", "this"];
+
+/// Methods and properties of `ComponentSlot` that give away a variable as a slot
+const SLOT_MEMBERS: [&str; 5] = [
+    "isEmpty",
+    "isNotEmpty",
+    "hasActualContent",
+    "toHtml",
+    "attributes",
+];
+
+/// The slots a single component template renders
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Slots {
+    /// Whether the default `$slot` is rendered
+    pub default: bool,
+    /// Variables used the way named slots are, either echoed on their
+    /// own as in `{{ $footer }}` or `{{ $footer ?? '' }}`, checked as in
+    /// `@isset($footer)` or inspected as in `$footer->isEmpty()`
+    pub named: Vec<Name>,
+    /// Every other variable the template reads, which may still be a slot
+    /// used in a way not recognised above, e.g. passed to a helper
+    pub read: Vec<Name>,
+}
+
+#[salsa::tracked]
+pub fn slots(db: &dyn DocumentDatabase, file: SourceFile) -> Arc<Slots> {
+    let mut slots = Slots::default();
+    let Some(doc) = &class_component::blade_document(db, file) else {
+        return Arc::new(slots);
+    };
+    let loop_variables = loop_variables(db, doc);
+    let variables = doc
+        .root_node()
+        .descendants()
+        .filter(|node| node.is::<ast::blade::VariableName>());
+    for variable in variables {
+        let Some(name) = doc
+            .text_for_node(db, variable)
+            .and_then(|text| text.strip_prefix('$'))
+        else {
+            continue;
+        };
+        if name == "slot" {
+            slots.default = true;
+            continue;
+        }
+        if RESERVED_VARIABLES.contains(&name) || loop_variables.contains(&name) {
+            continue;
+        }
+        let name = Name::new(name);
+        let list = match is_slot_usage(db, doc, variable) {
+            true => &mut slots.named,
+            false => &mut slots.read,
+        };
+        if !list.contains(&name) {
+            list.push(name);
+        }
+    }
+    let named = &slots.named;
+    slots.read.retain(|name| !named.contains(name));
+    Arc::new(slots)
+}
+
+/// Variables declared by loops, e.g. `$key` and `$item` in
+/// `@foreach ($items as $key => $item)`, which are never slots
+fn loop_variables<'doc>(
+    db: &'doc dyn DocumentDatabase,
+    doc: &'doc ParsedDocument,
+) -> Vec<&'doc str> {
+    let loops = doc.root_node().descendants().filter(|node| {
+        ast::node_is!(
+            *node,
+            ast::blade::ForeachDirective | ast::blade::ForelseDirective
+        )
+    });
+    let mut declarations = Vec::new();
+    for node in loops {
+        // The iterable comes first, then what follows `as`; the body
+        // is in a field of its own
+        let mut cursor = node.raw().walk();
+        let mut named = Vec::new();
+        if cursor.goto_first_child() {
+            loop {
+                if cursor.field_name().is_none() && cursor.node().is_named() {
+                    named.push(cursor.node());
+                }
+                if !cursor.goto_next_sibling() {
+                    break;
+                }
+            }
+        }
+        declarations.extend(named.into_iter().skip(1).map(UntypedNode::new));
+    }
+    declarations
+        .into_iter()
+        .flat_map(|declaration| declaration.descendants())
+        .filter(|node| node.is::<ast::blade::VariableName>())
+        .filter_map(|variable| doc.text_for_node(db, variable)?.strip_prefix('$'))
+        .collect()
+}
+
+fn is_slot_usage(
+    db: &dyn DocumentDatabase,
+    doc: &ParsedDocument,
+    variable: UntypedNode<'_>,
+) -> bool {
+    let Some(parent) = variable.parent() else {
+        return false;
+    };
+    if ast::node_is!(
+        parent,
+        ast::blade::Escaped
+            | ast::blade::Unescaped
+            | ast::blade::If
+            | ast::blade::Unless
+            | ast::blade::Isset
+            | ast::blade::Empty
+    ) {
+        return true;
+    }
+    // `{{ $footer ?? '' }}`
+    if parent.is::<ast::blade::BinaryExpression>() {
+        let raw = parent.raw();
+        return raw.child_by_field_name("left") == Some(*variable.raw())
+            && raw
+                .child_by_field_name("operator")
+                .and_then(|operator| doc.text_for_node(db, UntypedNode::new(operator)))
+                == Some("??");
+    }
+    if !ast::node_is!(
+        parent,
+        ast::blade::MemberCallExpression | ast::blade::MemberAccessExpression
+    ) {
+        return false;
+    }
+    let raw = parent.raw();
+    raw.child_by_field_name("object") == Some(*variable.raw())
+        && raw
+            .child_by_field_name("name")
+            .and_then(|member| doc.text_for_node(db, UntypedNode::new(member)))
+            .is_some_and(|member| SLOT_MEMBERS.contains(&member))
+}
+
+impl Slots {
+    pub fn for_document(db: &dyn DocumentDatabase, doc: &ParsedDocument) -> Arc<Slots> {
+        slots(db, doc.source)
+    }
+
+    /// Whether `name` can be filled in with `<x-slot:name>`. Laravel
+    /// camelCases slot names, so `<x-slot:footer-links>` fills `$footerLinks`.
+    pub fn contains(&self, name: &Name) -> bool {
+        self.named
+            .iter()
+            .chain(&self.read)
+            .any(|slot| attributes::same_attribute(slot.as_str(), name.as_str()))
+    }
+}
+
+/// A named slot filled in where a component is used, either with
+/// `<x-slot:footer>` or with `<x-slot name="footer">`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SlotFill {
+    pub name: Name,
+    /// Range of the slot name alone
+    pub range: TextRange,
+}
+
+impl SlotFill {
+    pub(crate) const PREFIX: &str = "x-slot:";
+
+    pub fn for_element(
+        db: &dyn DocumentDatabase,
+        doc: &ParsedDocument,
+        element: ast::blade::Element<'_>,
+    ) -> Option<SlotFill> {
+        let tag_name = element.tag_name()?;
+        let text = doc.text_for_node(db, tag_name)?;
+        if let Some(name) = text.strip_prefix(Self::PREFIX) {
+            let start = TextSize::new((tag_name.start_byte() + Self::PREFIX.len()) as u32);
+            return Some(SlotFill {
+                name: Name::new(name),
+                range: TextRange::at(start, TextSize::of(name)),
+            });
+        }
+        if text != "x-slot" {
+            return None;
+        }
+        let tag = element.tag()?;
+        let mut cursor = tag.walk();
+        let value = tag
+            .untyped_children(&mut cursor)
+            .filter(|child| child.is::<ast::blade::HtmlAttribute>())
+            .find(|attr| {
+                let mut cursor = attr.walk();
+                attr.untyped_children(&mut cursor)
+                    .next()
+                    .and_then(|attr_name| doc.text_for_node(db, attr_name))
+                    == Some("name")
+            })?
+            .descendants()
+            .find(|node| node.is::<ast::blade::AttributeValue>())?;
+        let name = doc.text_for_node(db, value)?;
+        Some(SlotFill {
+            name: Name::new(name),
+            range: TextRange::new(
+                TextSize::new(value.start_byte() as u32),
+                TextSize::new(value.end_byte() as u32),
+            ),
+        })
+    }
+
+    /// The component being filled in, which is the nearest tag around
+    /// `node` other than a slot. None if that is not a component, e.g. a layout.
+    pub fn component(
+        db: &dyn DefDatabase,
+        doc: &ParsedDocument,
+        node: UntypedNode<'_>,
+        root: &WorkspaceRoot,
+    ) -> Option<Component> {
+        let outer = Self::filled_element(db, doc, node)?.tag_name()?;
+        Component::for_tagname(db, outer, doc, root)
+    }
+
+    /// The nearest element around `node` other than a slot, which is the
+    /// element a slot at `node` fills in
+    pub fn filled_element<'tree>(
+        db: &dyn DocumentDatabase,
+        doc: &ParsedDocument,
+        node: UntypedNode<'tree>,
+    ) -> Option<ast::blade::Element<'tree>> {
+        node.ancestors()
+            .filter_map(|node| node.downcast::<ast::blade::Element>().ok())
+            .find(|element| {
+                element
+                    .tag_name()
+                    .and_then(|tag_name| doc.text_for_node(db, tag_name))
+                    .is_some_and(|text| text != "x-slot" && !text.starts_with(Self::PREFIX))
+            })
+    }
+}
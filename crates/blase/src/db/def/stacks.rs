@@ -0,0 +1,124 @@
+//! Stacks filled in with `@push` and rendered with `@stack`
+
+use std::sync::Arc;
+
+use ast::NodeExt;
+use line_index::TextRange;
+
+use crate::{
+    config::WorkspaceRoot,
+    db::{
+        DocumentDatabase, ParsedDocument, SourceFile,
+        def::{
+            ComponentName, Directive, LayoutName, Name, attributes::node_range, class_component,
+            inheritance::Template, name_argument, views,
+        },
+    },
+    resolve_path,
+};
+
+/// A stack name along with where it is written
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct StackSite {
+    pub directive: Directive,
+    pub name: Name,
+    pub range: TextRange,
+}
+
+/// The stacks a single template pushes to and renders
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Stacks {
+    /// Content pushed with `@push`, `@prepend` and their variants
+    pub pushes: Vec<StackSite>,
+    /// Places where stacks are rendered with `@stack`
+    pub renders: Vec<StackSite>,
+}
+
+#[salsa::tracked]
+pub fn stacks(db: &dyn DocumentDatabase, file: SourceFile) -> Arc<Stacks> {
+    let mut stacks = Stacks::default();
+    let Some(doc) = &class_component::blade_document(db, file) else {
+        return Arc::new(stacks);
+    };
+    for node in doc.root_node().descendants() {
+        let Some(directive) = Directive::for_directive_node(node) else {
+            continue;
+        };
+        let sites = match directive {
+            Directive::Stack => &mut stacks.renders,
+            Directive::Push
+            | Directive::PushOnce
+            | Directive::PushIf
+            | Directive::Prepend
+            | Directive::PrependOnce => &mut stacks.pushes,
+            _ => continue,
+        };
+        if let Some((name, range)) = name_argument(db, doc, node) {
+            sites.push(StackSite {
+                directive,
+                name,
+                range,
+            });
+        }
+    }
+    Arc::new(stacks)
+}
+
+impl Stacks {
+    pub fn for_document(db: &dyn DocumentDatabase, doc: &ParsedDocument) -> Arc<Stacks> {
+        stacks(db, doc.source)
+    }
+
+    /// Stack names rendered by the views that content pushed at `push` ends
+    /// up in: the layouts the document extends, the components and layouts
+    /// wrapped around the push, and every view those include.
+    ///
+    /// Returns None when that can't be known, e.g. for pushes in partials
+    /// and components which are rendered from elsewhere.
+    pub fn rendered_around(
+        db: &dyn DocumentDatabase,
+        doc: &ParsedDocument,
+        root: &WorkspaceRoot,
+        push: &StackSite,
+    ) -> Option<Vec<Name>> {
+        let template = Template::for_document(db, doc);
+        let mut hosts = Vec::new();
+        if template.extends.is_some() {
+            if !Template::is_chain_complete(db, doc, root) {
+                return None;
+            }
+            hosts.extend(Template::ancestors(db, doc, root));
+        }
+        let tags = doc
+            .root_node()
+            .descendants()
+            .filter_map(|node| node.downcast::<ast::blade::Element>().ok())
+            .filter(|element| node_range(*element).contains_range(push.range))
+            .filter_map(|element| doc.text_for_node(db, element.tag_name()?));
+        for tag in tags {
+            let resources_path = if let Some(layout) = LayoutName::new(tag) {
+                Some(resolve_path::layout_paths(db, &layout, root).1)
+            } else if let Some(component) = ComponentName::new(tag) {
+                resolve_path::component_template(db, &component, root)
+            } else {
+                continue;
+            };
+            let Some(host) = resources_path.and_then(|path| db.parsed_document(&path)) else {
+                continue;
+            };
+            hosts.extend(Template::ancestors(db, &host, root));
+            hosts.push(host);
+        }
+        if hosts.is_empty() {
+            return None;
+        }
+        hosts.push(doc.clone());
+        let names = hosts
+            .iter()
+            .flat_map(|host| views::with_included_views(db, host, root))
+            .flat_map(|view| Self::for_document(db, &view).renders.clone())
+            .map(|site| site.name)
+            .collect();
+        Some(names)
+    }
+}
@@ -0,0 +1,187 @@
+//! Views referenced by name from directives such as `@include`
+
+use std::collections::HashSet;
+
+use ast::NodeExt;
+use line_index::{TextRange, TextSize};
+use type_sitter::{Node, UntypedNode};
+
+use crate::{
+    config::WorkspaceRoot,
+    db::{
+        DocumentDatabase, ParsedDocument, SourceFile,
+        def::{
+            ArgumentKind, Directive, Document, DocumentId, ViewName, directive_arguments,
+            is_string_literal, unquote,
+        },
+    },
+    resolve_path,
+};
+
+#[salsa::interned(no_lifetime)]
+#[derive(Debug)]
+pub struct ViewId {
+    pub file: SourceFile,
+    pub name: ViewName,
+}
+
+impl DocumentId for ViewId {
+    fn file(&self, db: &dyn DocumentDatabase) -> SourceFile {
+        ViewId::file(*self, db)
+    }
+}
+
+/// A Blade template rendered by name, e.g. a partial
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct View {
+    pub id: ViewId,
+}
+
+impl Document for View {
+    type Id = ViewId;
+    fn id(&self) -> Self::Id {
+        self.id
+    }
+}
+
+impl View {
+    pub fn name(&self, db: &dyn DocumentDatabase) -> ViewName {
+        self.id.name(db)
+    }
+
+    pub fn from_name(
+        db: &dyn DocumentDatabase,
+        name: ViewName,
+        root: &WorkspaceRoot,
+    ) -> Option<Self> {
+        let path = resolve_path::view_path(db, &name, root);
+        let doc = db.parsed_document(&path)?;
+        let id = ViewId::new(db, doc.source, name);
+        Some(Self { id })
+    }
+}
+
+/// A view name passed as an argument to a directive
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ViewReference {
+    pub directive: Directive,
+    pub name: ViewName,
+    /// Range of the string literal, including its quotes
+    pub range: TextRange,
+}
+
+impl ViewReference {
+    pub fn all(db: &dyn DocumentDatabase, doc: &ParsedDocument) -> Vec<ViewReference> {
+        doc.root_node()
+            .descendants()
+            .filter(|node| node.is::<ast::blade::InlineDirective>())
+            .flat_map(|node| Self::for_directive(db, doc, node))
+            .collect()
+    }
+
+    /// Finds the view name under a node, if it is inside a directive argument
+    pub fn for_node(
+        db: &dyn DocumentDatabase,
+        doc: &ParsedDocument,
+        node: UntypedNode<'_>,
+    ) -> Option<ViewReference> {
+        let directive = node
+            .ancestors()
+            .find(|node| node.is::<ast::blade::InlineDirective>())?;
+        let offset = TextSize::new(node.start_byte() as u32);
+        Self::for_directive(db, doc, directive)
+            .into_iter()
+            .find(|reference| reference.range.contains_inclusive(offset))
+    }
+
+    /// Collects the view names passed to a single directive
+    pub fn for_directive(
+        db: &dyn DocumentDatabase,
+        doc: &ParsedDocument,
+        node: UntypedNode<'_>,
+    ) -> Vec<ViewReference> {
+        let Some(directive) = Directive::for_directive_node(node) else {
+            return Vec::new();
+        };
+        view_literals(directive, node)
+            .filter_map(|literal| {
+                let text = doc.text_for_node(db, literal)?;
+                let name = ViewName::new(unquote(text)?)?;
+                let range = TextRange::new(
+                    TextSize::new(literal.start_byte() as u32),
+                    TextSize::new(literal.end_byte() as u32),
+                );
+                Some(ViewReference {
+                    directive,
+                    name,
+                    range,
+                })
+            })
+            .collect()
+    }
+
+    pub fn path(&self, db: &dyn DocumentDatabase, root: &WorkspaceRoot) -> camino::Utf8PathBuf {
+        resolve_path::view_path(db, &self.name, root)
+    }
+
+    pub fn resolve(&self, db: &dyn DocumentDatabase, root: &WorkspaceRoot) -> Option<View> {
+        View::from_name(db, self.name.clone(), root)
+    }
+}
+
+/// The document itself and every view it includes, directly or indirectly
+pub fn with_included_views(
+    db: &dyn DocumentDatabase,
+    doc: &ParsedDocument,
+    root: &WorkspaceRoot,
+) -> Vec<ParsedDocument> {
+    let mut seen = HashSet::new();
+    let mut views = Vec::new();
+    let mut stack = vec![doc.clone()];
+    while let Some(doc) = stack.pop() {
+        if !seen.insert(doc.source) {
+            continue;
+        }
+        let included = ViewReference::all(db, &doc)
+            .into_iter()
+            .filter(|reference| reference.directive != Directive::Extends)
+            .filter_map(|reference| db.parsed_document(&reference.path(db, root)));
+        stack.extend(included);
+        views.push(doc);
+    }
+    views
+}
+
+/// The string literals naming views among the arguments of a directive.
+/// Only a literal passed as is counts, or one standing on its own in the
+/// array of candidates `@includeFirst` takes. A name that is computed, e.g.
+/// `'cards.' . $size`, can't be told from the literals in it.
+fn view_literals<'tree>(
+    directive: Directive,
+    node: UntypedNode<'tree>,
+) -> impl Iterator<Item = UntypedNode<'tree>> {
+    directive_arguments(node)
+        .enumerate()
+        .filter(move |(idx, _)| directive.argument_kind(*idx) == Some(ArgumentKind::View))
+        .flat_map(move |(_, arg)| {
+            if is_string_literal(arg) {
+                return vec![arg];
+            }
+            if directive != Directive::IncludeFirst
+                || !arg.is::<ast::blade::ArrayCreationExpression>()
+            {
+                return Vec::new();
+            }
+            arg.descendants()
+                .filter(|node| is_string_literal(*node))
+                .filter(|literal| {
+                    literal.parent().is_some_and(|parent| {
+                        parent.is::<ast::blade::ArrayElementValueInitializer>()
+                    }) && literal
+                        .ancestors()
+                        .find(|node| node.is::<ast::blade::ArrayCreationExpression>())
+                        .is_some_and(|array| array.raw() == arg.raw())
+                })
+                .collect()
+        })
+}
@@ -1,6 +1,9 @@
 use type_sitter::{HasChildren, Node};
 
-use crate::db::def::{Component, DefDatabase, Document, DocumentId, Layout};
+use crate::db::{
+    ParsedDocument,
+    def::{Component, DefDatabase, Document, DocumentId, Layout, views::View},
+};
 
 /// Holds documentation
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
@@ -25,25 +28,35 @@ impl From<Documentation> for String {
 
 pub trait HasDocs: Document + Copy {
     fn docs(&self, db: &dyn DefDatabase) -> Option<Documentation> {
-        let id = self.id();
-        let document = &id.document(db);
-        let doc = document
-            .root_node()
-            .downcast::<ast::blade::Document>()
-            .ok()?;
-        let child = doc.children(&mut doc.walk()).next()?.ok()?;
-        use ast::blade::anon_unions::Anon122367149080002252186915888317997925741::Comment;
-        let comment = match child {
-            Comment(comment) => document.text_for_node(db, comment)?,
-            _ => return None,
-        };
-        extract_docs(comment)
+        template_docs(db, &self.id().document(db))
     }
 }
 
 impl HasDocs for Layout {}
 
-impl HasDocs for Component {}
+impl HasDocs for Component {
+    /// A class component is documented in the template it renders
+    fn docs(&self, db: &dyn DefDatabase) -> Option<Documentation> {
+        template_docs(db, &self.template(db)?)
+    }
+}
+
+impl HasDocs for View {}
+
+/// The comment that opens a template
+fn template_docs(db: &dyn DefDatabase, document: &ParsedDocument) -> Option<Documentation> {
+    let doc = document
+        .root_node()
+        .downcast::<ast::blade::Document>()
+        .ok()?;
+    let child = doc.children(&mut doc.walk()).next()?.ok()?;
+    use ast::blade::anon_unions::Anon122367149080002252186915888317997925741::Comment;
+    let comment = match child {
+        Comment(comment) => document.text_for_node(db, comment)?,
+        _ => return None,
+    };
+    extract_docs(comment)
+}
 
 fn extract_docs(comment: &str) -> Option<Documentation> {
     let text = comment.strip_prefix("{{")?.strip_suffix("}}")?;
@@ -0,0 +1,84 @@
+//! Adapted from https://github.com/rust-lang/rust-analyzer/blob/b8458013c217be4fccefc4e4f194026fa04ab4ca/crates/ide-db/src/source_change.rs
+//! A change that spans several files, such as the result of a rename.
+
+use std::collections::HashMap;
+
+use camino::Utf8PathBuf;
+
+use crate::db::text_edit::{TextEdit, TextEditBuilder};
+
+#[derive(Default, Debug, Clone)]
+pub struct SourceChange {
+    /// Offsets refer to the files before any of `file_system_edits` happen
+    pub source_file_edits: HashMap<Utf8PathBuf, TextEdit>,
+    pub file_system_edits: Vec<FileSystemEdit>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum FileSystemEdit {
+    MoveFile { src: Utf8PathBuf, dst: Utf8PathBuf },
+}
+
+impl SourceChange {
+    /// Adds `edit` to the edits of `path`. Edits already there win over
+    /// the parts of `edit` that overlap with them, which are dropped.
+    pub fn insert_source_edit(&mut self, path: Utf8PathBuf, edit: TextEdit) {
+        match self.source_file_edits.entry(path) {
+            std::collections::hash_map::Entry::Occupied(mut entry) => {
+                let Err(edit) = entry.get_mut().union(edit) else {
+                    return;
+                };
+                for indel in edit {
+                    let indel = TextEdit::replace(indel.delete, indel.insert);
+                    if let Err(indel) = entry.get_mut().union(indel) {
+                        tracing::warn!(path = %entry.key(), ?indel, "dropped overlapping edit");
+                    }
+                }
+            }
+            std::collections::hash_map::Entry::Vacant(entry) => {
+                entry.insert(edit);
+            }
+        }
+    }
+
+    pub fn insert_source_edits(&mut self, path: Utf8PathBuf, builder: TextEditBuilder) {
+        if !builder.is_empty() {
+            self.insert_source_edit(path, builder.finish());
+        }
+    }
+
+    pub fn push_file_system_edit(&mut self, edit: FileSystemEdit) {
+        self.file_system_edits.push(edit);
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.source_file_edits.is_empty() && self.file_system_edits.is_empty()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use camino::Utf8Path;
+    use line_index::TextRange;
+
+    use super::{SourceChange, TextEdit, TextEditBuilder};
+
+    fn range(start: u32, end: u32) -> TextRange {
+        TextRange::new(start.into(), end.into())
+    }
+
+    #[test]
+    fn test_insert_overlapping_edits() {
+        let path = Utf8Path::new("a.blade.php");
+        let mut change = SourceChange::default();
+        change.insert_source_edit(path.into(), TextEdit::replace(range(1, 5), "x".to_owned()));
+        let mut builder = TextEditBuilder::default();
+        builder.replace(range(3, 7), "y".to_owned());
+        builder.replace(range(9, 11), "z".to_owned());
+        change.insert_source_edits(path.into(), builder);
+
+        let mut text = "0123456789ab".to_owned();
+        change.source_file_edits[path].apply(&mut text);
+        assert_eq!(text, "0x5678zb");
+    }
+}
@@ -2,12 +2,14 @@
 //! Protocol. This module specifically handles notifications.
 
 use async_lsp::lsp_types::{
-    DidChangeTextDocumentParams, DidCloseTextDocumentParams, DidOpenTextDocumentParams,
-    DidSaveTextDocumentParams, InitializedParams,
+    DidChangeConfigurationParams, DidChangeTextDocumentParams, DidChangeWatchedFilesParams,
+    DidChangeWorkspaceFoldersParams, DidCloseTextDocumentParams, DidOpenTextDocumentParams,
+    DidSaveTextDocumentParams, FileChangeType, InitializedParams,
 };
+use itertools::Itertools;
 use std::ops::ControlFlow;
 
-use crate::{document_data::DocumentData, lsp, server::ServerState};
+use crate::{document_data::DocumentData, lsp, server::ServerState, util::FileType};
 
 #[tracing::instrument(skip(server))]
 pub fn handle_did_save(
@@ -16,7 +18,11 @@ pub fn handle_did_save(
 ) -> ControlFlow<async_lsp::Result<()>> {
     let path = lsp::from_proto::utf8_path(&text_document.uri);
     let analysis = server.snapshot().analysis;
-    if let Ok(diagnostics) = analysis.parse_errors(&path) {
+    // Syntax errors of PHP files come with the full diagnostics, which only
+    // report those of inline templates
+    if FileType::from_path(&path) == Some(FileType::Blade)
+        && let Ok(diagnostics) = analysis.parse_errors(&path)
+    {
         server.publish_diagnostics(
             text_document.uri,
             diagnostics.into_iter().map(Into::into).collect(),
@@ -39,6 +45,14 @@ pub fn handle_did_close(
     let path = lsp::from_proto::utf8_path(&text_document.uri);
     if server.documents.remove(&path).is_none() {
         tracing::error!(url = path.as_str(), "orphan DidCloseTextDocument");
+        return ControlFlow::Continue(());
+    }
+    // Unsaved changes are gone, so the file on disk applies again
+    match std::fs::read_to_string(&path) {
+        Ok(contents) => server.analysis_host.set_source_file(path, &contents),
+        Err(_) => {
+            server.analysis_host.remove_source_file(&path);
+        }
     }
     ControlFlow::Continue(())
 }
@@ -85,6 +99,11 @@ pub fn handle_did_open(
         &path,
         document.contents.len()
     );
+    // The buffer may be ahead of the disk, or the file may not be under
+    // any of the directories loaded with the workspace
+    server
+        .analysis_host
+        .set_source_file(path.clone(), &document.contents);
     server.documents.insert(path.clone(), document);
 
     if let Err(e) = server.emit(crate::handler::Event::DiagnosticUpdate(path)) {
@@ -99,6 +118,8 @@ pub fn handle_initialized(
     server: &mut ServerState,
     _params: InitializedParams,
 ) -> ControlFlow<async_lsp::Result<()>> {
+    server.register_file_watchers();
+
     let token = "blase/load_workspace".to_string();
     let progress_sender = server.with_report_progress(token);
 
@@ -109,3 +130,140 @@ pub fn handle_initialized(
 
     ControlFlow::Continue(())
 }
+
+/// Re-indexes the workspace when the project layout changes, since files
+/// may have moved in or out of view and component roots
+#[tracing::instrument(skip(server))]
+pub fn handle_did_change_configuration(
+    server: &mut ServerState,
+    DidChangeConfigurationParams { settings }: DidChangeConfigurationParams,
+) -> ControlFlow<async_lsp::Result<()>> {
+    let changed = server
+        .config
+        .write()
+        .expect("poison")
+        .apply_settings(settings);
+    if !changed {
+        return ControlFlow::Continue(());
+    }
+
+    // The watchers follow the directories of the new layout
+    server.unregister_file_watchers();
+    server.register_file_watchers();
+
+    let token = "blase/load_workspace".to_string();
+    let progress_sender = server.with_report_progress(token);
+    if let Err(e) = server.load_workspace(progress_sender) {
+        return ControlFlow::Break(Err(e));
+    };
+
+    ControlFlow::Continue(())
+}
+
+/// Keeps the analysis in sync with files created, changed and deleted
+/// outside the editor. Files open in the editor are left alone, since
+/// their buffers are what the analysis should see.
+#[tracing::instrument(skip(server))]
+pub fn handle_did_change_watched_files(
+    server: &mut ServerState,
+    DidChangeWatchedFilesParams { changes }: DidChangeWatchedFilesParams,
+) -> ControlFlow<async_lsp::Result<()>> {
+    // A file may have been created in a directory a `*` in the layout now
+    // matches, e.g. a new module
+    let mut changed = server.config.write().expect("poison").refresh_roots();
+    for change in changes {
+        let path = lsp::from_proto::utf8_path(&change.uri);
+        if FileType::from_path(&path).is_none() || server.documents.contains_key(&path) {
+            continue;
+        }
+        match change.typ {
+            FileChangeType::CREATED | FileChangeType::CHANGED => {
+                match std::fs::read_to_string(&path) {
+                    Ok(contents) => {
+                        server.analysis_host.set_source_file(path, &contents);
+                        changed = true;
+                    }
+                    Err(e) => tracing::error!(url = path.as_str(), error = %e, "cannot read file"),
+                }
+            }
+            FileChangeType::DELETED => {
+                if server.analysis_host.remove_source_file(&path) {
+                    server.publish_diagnostics(change.uri, Vec::new(), None);
+                    changed = true;
+                }
+            }
+            _ => {}
+        }
+    }
+    if !changed {
+        return ControlFlow::Continue(());
+    }
+
+    // Any template may use a component, layout or view that just appeared
+    // or went away, so those open or with diagnostics shown are checked
+    // again. The rest are checked once they are opened.
+    let paths = server
+        .documents
+        .iter()
+        .map(|entry| entry.key().clone())
+        .chain(server.diagnosed.iter().cloned())
+        .unique()
+        .collect::<Vec<_>>();
+    for path in paths {
+        if let Err(e) = server.emit(crate::handler::Event::DiagnosticUpdate(path)) {
+            return ControlFlow::Break(Err(e));
+        }
+    }
+
+    ControlFlow::Continue(())
+}
+
+/// Adds and removes roots as folders are added to and removed from the
+/// workspace, along with the files only they held
+#[tracing::instrument(skip(server))]
+pub fn handle_did_change_workspace_folders(
+    server: &mut ServerState,
+    DidChangeWorkspaceFoldersParams { event }: DidChangeWorkspaceFoldersParams,
+) -> ControlFlow<async_lsp::Result<()>> {
+    let removed = event
+        .removed
+        .iter()
+        .map(|folder| lsp::from_proto::utf8_path(&folder.uri))
+        .collect::<Vec<_>>();
+    let stale = {
+        let mut config = server.config.write().expect("poison");
+        config.roots.retain(|root| !removed.contains(&root.folder));
+        for folder in event.added {
+            let path = lsp::from_proto::utf8_path(&folder.uri);
+            config.add_root(folder.name, path);
+        }
+        server
+            .analysis_host
+            .raw_database()
+            .file_paths()
+            .filter(|path| removed.iter().any(|folder| path.starts_with(folder)))
+            .filter(|path| {
+                !config
+                    .roots
+                    .iter()
+                    .any(|root| path.starts_with(&root.folder))
+            })
+            .filter(|path| !server.documents.contains_key(path))
+            .collect::<Vec<_>>()
+    };
+    for path in stale {
+        server.analysis_host.remove_source_file(&path);
+        server.publish_diagnostics(lsp::into_proto::url(&path), Vec::new(), None);
+    }
+
+    server.unregister_file_watchers();
+    server.register_file_watchers();
+
+    let token = "blase/load_workspace".to_string();
+    let progress_sender = server.with_report_progress(token);
+    if let Err(e) = server.load_workspace(progress_sender) {
+        return ControlFlow::Break(Err(e));
+    };
+
+    ControlFlow::Continue(())
+}
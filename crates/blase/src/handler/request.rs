@@ -2,17 +2,18 @@
 //! Protocol. This module specifically handles requests.
 
 use async_lsp::{
-    ErrorCode, ResponseError,
+    ResponseError,
     lsp_types::{
-        self, CompletionParams, CompletionResponse, GotoDefinitionParams, GotoDefinitionResponse,
-        Hover, HoverContents, HoverParams, InitializeParams, InitializeResult, Location,
-        MarkupContent, MarkupKind, ReferenceParams, ServerInfo, SignatureHelp, SignatureHelpParams,
-        WorkspaceSymbolParams, WorkspaceSymbolResponse,
+        self, CodeActionOrCommand, CodeActionParams, CodeActionResponse, CompletionParams,
+        CompletionResponse, GotoDefinitionParams, GotoDefinitionResponse, Hover, HoverContents,
+        HoverParams, InitializeParams, InitializeResult, Location, MarkupContent, MarkupKind,
+        PrepareRenameResponse, ReferenceParams, RenameParams, ServerInfo, SignatureHelp,
+        SignatureHelpParams, TextDocumentPositionParams, WorkspaceEdit, WorkspaceSymbolParams,
+        WorkspaceSymbolResponse,
     },
 };
 use camino::Utf8PathBuf;
 use itertools::Itertools;
-use line_index::TextRange;
 
 use crate::{
     config,
@@ -68,12 +69,7 @@ pub fn handle_references(
         .into_iter()
         .flat_map(|refs| {
             let defs = if include_declaration {
-                refs.defined_files.map(|files| {
-                    files.into_iter().map(|path| FileRange {
-                        path,
-                        range: TextRange::default(),
-                    })
-                })
+                refs.definitions.map(|definitions| definitions.into_iter())
             } else {
                 None
             }
@@ -232,6 +228,83 @@ pub fn handle_hover(
     Ok(hover_result())
 }
 
+pub fn handle_prepare_rename(
+    snap: ServerStateSnapshot,
+    params: TextDocumentPositionParams,
+) -> Result<Option<PrepareRenameResponse>, ResponseError> {
+    let _i = tracing::info_span!("handle_prepare_rename").entered();
+    let position =
+        match lsp::into_proto::cancellable(lsp::from_proto::file_position(&snap, &params))? {
+            Some(position) => position,
+            None => return Ok(None),
+        };
+    let line_index = match lsp::into_proto::cancellable(snap.file_line_index(&position.path))? {
+        Some(index) => index,
+        None => return Ok(None),
+    };
+    let config = snap.config.read().expect("poison");
+    let range = lsp::into_proto::cancellable(snap.analysis.prepare_rename(&config, position))?
+        .map_err(lsp::into_proto::rename_error)?;
+    Ok(Some(PrepareRenameResponse::Range(lsp::into_proto::range(
+        &line_index,
+        range,
+    ))))
+}
+
+pub fn handle_rename(
+    snap: ServerStateSnapshot,
+    params: RenameParams,
+) -> Result<Option<WorkspaceEdit>, ResponseError> {
+    let _i = tracing::info_span!("handle_rename").entered();
+    let position = match lsp::into_proto::cancellable(lsp::from_proto::file_position(
+        &snap,
+        &params.text_document_position,
+    ))? {
+        Some(position) => position,
+        None => return Ok(None),
+    };
+    let config = snap.config.read().expect("poison");
+    let change =
+        lsp::into_proto::cancellable(snap.analysis.rename(&config, position, &params.new_name))?
+            .map_err(lsp::into_proto::rename_error)?;
+    let edit = lsp::into_proto::cancellable(lsp::into_proto::workspace_edit(&snap, change))?;
+    Ok(Some(edit))
+}
+
+pub fn handle_code_action(
+    snap: ServerStateSnapshot,
+    params: CodeActionParams,
+) -> Result<Option<CodeActionResponse>, ResponseError> {
+    let _i = tracing::info_span!("handle_code_action").entered();
+    let Some(frange) = lsp::into_proto::cancellable(lsp::from_proto::file_range(
+        &snap,
+        &params.text_document,
+        params.range,
+    ))?
+    else {
+        return Ok(None);
+    };
+    let Some(line_index) = lsp::into_proto::cancellable(snap.file_line_index(&frange.path))? else {
+        return Ok(None);
+    };
+    let config = snap.config.read().expect("poison");
+    let diagnostics =
+        lsp::into_proto::cancellable(snap.analysis.full_diagnostics(&config, &frange.path))?;
+    let mut actions = Vec::new();
+    for diagnostic in diagnostics {
+        if diagnostic.range.range.intersect(frange.range).is_none() {
+            continue;
+        }
+        let action = lsp::into_proto::cancellable(lsp::into_proto::quick_fix(
+            &snap,
+            &line_index,
+            diagnostic,
+        ))?;
+        actions.extend(action.map(CodeActionOrCommand::CodeAction));
+    }
+    Ok(Some(actions))
+}
+
 pub fn handle_initialize(
     server: &mut ServerState,
     params: InitializeParams,
@@ -244,19 +317,10 @@ pub fn handle_initialize(
 
     match params.workspace_folders {
         None => {
-            tracing::info!(
-                workspace_folder = %server.config.read().expect("poison").workspace_folder,
-                "using current directory",
-            );
+            let config = server.config.read().expect("poison");
+            tracing::info!(roots = ?config.roots, "using current directory");
         }
         Some(folders) => {
-            if folders.len() > 1 {
-                let err = ResponseError::new(
-                    ErrorCode::REQUEST_FAILED,
-                    "Multiple workspaces not yet supported",
-                );
-                return Box::pin(async move { Err(err) });
-            }
             let mut config = server.config.write().expect("poison");
             config.client_info =
                 params
@@ -266,13 +330,22 @@ pub fn handle_initialize(
                     });
             tracing::info!(client=?config.client_info);
             config.capabilities = params.capabilities;
-            let workspace_folder = folders[0].clone();
-            tracing::debug!(url = workspace_folder.uri.path());
-            let work = workspace_folder
-                .uri
-                .to_file_path()
-                .expect("file:// urls should always be valid file paths");
-            config.workspace_folder = Utf8PathBuf::from_path_buf(work).unwrap();
+            config.roots.clear();
+            for folder in folders {
+                tracing::debug!(url = folder.uri.path());
+                let work = folder
+                    .uri
+                    .to_file_path()
+                    .expect("file:// urls should always be valid file paths");
+                config.add_root(folder.name, Utf8PathBuf::from_path_buf(work).unwrap());
+            }
+        }
+    }
+
+    if let Some(options) = params.initialization_options {
+        let mut config = server.config.write().expect("poison");
+        if config.apply_settings(options) {
+            tracing::info!("using project layout from initialization options");
         }
     }
 
@@ -1,14 +1,17 @@
-use std::path::Path;
+use std::{
+    collections::HashSet,
+    path::{Path, PathBuf},
+};
 
 use async_lsp::lsp_types::{
     ProgressParamsValue, WorkDoneProgress, WorkDoneProgressBegin, WorkDoneProgressEnd,
     WorkDoneProgressReport,
 };
-use camino::Utf8PathBuf;
+use camino::{Utf8Path, Utf8PathBuf};
 use crossbeam_channel::{Sender, unbounded};
 use walkdir::{DirEntry, WalkDir};
 
-use crate::server::ServerState;
+use crate::{lsp, resolve_path, server::ServerState};
 
 fn walk_files<P: AsRef<Path>>(path: P) -> impl Iterator<Item = DirEntry> {
     WalkDir::new(path.as_ref())
@@ -17,18 +20,71 @@ fn walk_files<P: AsRef<Path>>(path: P) -> impl Iterator<Item = DirEntry> {
         .filter(|e| e.path().is_file() && e.path().is_absolute())
 }
 
+/// Package directories, which are laid out as `vendor/<vendor>/<package>`
+fn packages(vendor_dir: &Path) -> impl Iterator<Item = PathBuf> {
+    let subdirs = |dir: &Path| {
+        std::fs::read_dir(dir)
+            .into_iter()
+            .flatten()
+            .filter_map(Result::ok)
+            .map(|entry| entry.path())
+            .filter(|path| path.is_dir())
+    };
+    subdirs(vendor_dir).flat_map(move |vendor| subdirs(&vendor).collect::<Vec<_>>())
+}
+
 impl ServerState {
     pub fn load_workspace(
         &mut self,
         progress_sender: Sender<ProgressParamsValue>,
     ) -> async_lsp::Result<()> {
         let _p = tracing::info_span!("load_workspace").entered();
-        let workspace = self.config.read().expect("poison").workspace_folder.clone();
+        let config = self.config.read().expect("poison");
 
-        tracing::info!("loading workspace at: {:?}", workspace.as_path());
+        let mut entries = Vec::new();
+        for root in &config.roots {
+            let workspace = &root.folder;
+            tracing::info!("loading workspace at: {:?}", workspace.as_path());
+
+            for dir in root.view_roots().chain(root.component_class_roots()) {
+                entries.extend(walk_files(dir));
+            }
+            for (_, dir) in root.namespaces() {
+                entries.extend(walk_files(dir));
+            }
+            entries.extend(walk_files(workspace.join(resolve_path::providers_dir())));
+            for package in packages(workspace.join(resolve_path::vendor_dir()).as_std_path()) {
+                entries.extend(walk_files(
+                    package.join(resolve_path::component_views_dir()),
+                ));
+                entries.extend(walk_files(
+                    package.join(resolve_path::package_component_class_dir()),
+                ));
+            }
+        }
+        drop(config);
+        // Roots may be nested in one another, as may workspace folders
+        entries.sort_by(|a, b| a.path().cmp(b.path()));
+        entries.dedup_by(|a, b| a.path() == b.path());
 
-        let mut entries = walk_files(&workspace.join("resources/views")).collect::<Vec<_>>();
-        entries.extend(walk_files(&workspace.join("app/View")));
+        // Files the roots no longer hold, e.g. after their layout was
+        // changed in the settings, are dropped unless they are open
+        let stale = {
+            let found = entries
+                .iter()
+                .filter_map(|entry| Utf8Path::from_path(entry.path()))
+                .collect::<HashSet<_>>();
+            self.analysis_host
+                .raw_database()
+                .file_paths()
+                .filter(|path| !found.contains(path.as_path()))
+                .filter(|path| !self.documents.contains_key(path))
+                .collect::<Vec<_>>()
+        };
+        for path in stale {
+            self.analysis_host.remove_source_file(&path);
+            self.publish_diagnostics(lsp::into_proto::url(&path), Vec::new(), None);
+        }
 
         let total_entries = entries.len();
 
@@ -69,7 +125,10 @@ impl ServerState {
         });
         while let Ok((path, contents)) = rx.recv() {
             tracing::trace!(%path, len = contents.len());
-            self.analysis_host.set_source_file(path.clone(), &contents);
+            // Open documents are kept in sync with their buffers instead
+            if !self.documents.contains_key(&path) {
+                self.analysis_host.set_source_file(path.clone(), &contents);
+            }
             self.emit(crate::handler::Event::DiagnosticUpdate(path))?;
         }
         Ok(())
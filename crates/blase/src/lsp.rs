@@ -2,10 +2,14 @@
 
 use async_lsp::{
     LanguageClient,
-    lsp_types::{Diagnostic, PublishDiagnosticsParams, Url},
+    lsp_types::{
+        Diagnostic, PublishDiagnosticsParams, Registration, RegistrationParams, Unregistration,
+        UnregistrationParams, Url,
+        notification::{DidChangeWatchedFiles, Notification},
+    },
 };
 
-use crate::{handler::Event, server::ServerState};
+use crate::{capabilities, handler::Event, server::ServerState};
 
 impl ServerState {
     pub fn publish_diagnostics(
@@ -14,6 +18,12 @@ impl ServerState {
         diagnostics: Vec<Diagnostic>,
         version: Option<i32>,
     ) {
+        let path = from_proto::utf8_path(&uri);
+        if diagnostics.is_empty() {
+            self.diagnosed.remove(&path);
+        } else {
+            self.diagnosed.insert(path);
+        }
         if let Err(e) = self.client.publish_diagnostics(PublishDiagnosticsParams {
             uri,
             diagnostics,
@@ -23,6 +33,44 @@ impl ServerState {
         };
     }
 
+    pub fn register_file_watchers(&mut self) {
+        let options = {
+            let config = self.config.read().expect("poison");
+            if !config.did_change_watched_files_dynamic_registration() {
+                return;
+            }
+            capabilities::file_watchers(&config)
+        };
+        let registration = Registration {
+            id: DidChangeWatchedFiles::METHOD.to_string(),
+            method: DidChangeWatchedFiles::METHOD.to_string(),
+            register_options: Some(serde_json::to_value(options).expect("Failed to serialize")),
+        };
+        // The request is queued as soon as it's made, and there's nothing
+        // to do with the response
+        drop(self.client.register_capability(RegistrationParams {
+            registrations: vec![registration],
+        }));
+    }
+
+    pub fn unregister_file_watchers(&mut self) {
+        if !self
+            .config
+            .read()
+            .expect("poison")
+            .did_change_watched_files_dynamic_registration()
+        {
+            return;
+        }
+        let unregistration = Unregistration {
+            id: DidChangeWatchedFiles::METHOD.to_string(),
+            method: DidChangeWatchedFiles::METHOD.to_string(),
+        };
+        drop(self.client.unregister_capability(UnregistrationParams {
+            unregisterations: vec![unregistration],
+        }));
+    }
+
     pub fn emit(&mut self, event: Event) -> async_lsp::Result<()> {
         self.client.emit(event)
     }
@@ -1,10 +1,16 @@
 //! Converts **from** lsp_types
-use async_lsp::lsp_types::{Position, Range, TextDocumentPositionParams, Url};
+use async_lsp::lsp_types::{
+    Position, Range, TextDocumentIdentifier, TextDocumentPositionParams, Url,
+};
 use camino::Utf8PathBuf;
-use line_index::LineCol;
+use line_index::{LineCol, TextRange};
 use tree_sitter::Point;
 
-use crate::{analysis::Cancellable, db::FilePosition, server::ServerStateSnapshot};
+use crate::{
+    analysis::Cancellable,
+    db::{FilePosition, FileRange},
+    server::ServerStateSnapshot,
+};
 
 pub fn file_position(
     snap: &ServerStateSnapshot,
@@ -17,6 +23,21 @@ pub fn file_position(
     Ok(position)
 }
 
+pub fn file_range(
+    snap: &ServerStateSnapshot,
+    text_document: &TextDocumentIdentifier,
+    range: Range,
+) -> Cancellable<Option<FileRange>> {
+    let path = utf8_path(&text_document.uri);
+    let line_index = snap.file_line_index(&path)?;
+    let range = line_index.and_then(|line| {
+        let start = line.index.offset(line_col(range.start))?;
+        let end = line.index.offset(line_col(range.end))?;
+        Some(TextRange::new(start, end))
+    });
+    Ok(range.map(|range| FileRange { path, range }))
+}
+
 pub fn line_col(position: Position) -> LineCol {
     LineCol {
         line: position.line,
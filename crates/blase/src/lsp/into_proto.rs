@@ -5,15 +5,20 @@ use crate::{
     analysis::{
         self, Cancellable,
         completions::{self, CompletionItemKind, CompletionRelevance},
-        signature_help, workspace_symbols,
+        rename, signature_help, workspace_symbols,
     },
     config::Config,
-    db::{self, FileRange, text_edit::InsertDelete},
+    db::{
+        self, FileRange,
+        source_change::{FileSystemEdit, SourceChange},
+        text_edit::InsertDelete,
+    },
     line_index::{LineEndings, LineIndex, PositionEncoding},
     server::ServerStateSnapshot,
 };
 use async_lsp::lsp_types::{self, CompletionResponse, Position, Range, Url};
 use camino::Utf8Path;
+use itertools::Itertools;
 use line_index::{TextRange, TextSize};
 use tree_sitter::Point;
 
@@ -59,6 +64,26 @@ pub fn diagnostic(line_index: &LineIndex, d: analysis::Diagnostic) -> lsp_types:
     }
 }
 
+/// The quick fix of a diagnostic, if it has one
+pub(crate) fn quick_fix(
+    snap: &ServerStateSnapshot,
+    line_index: &LineIndex,
+    mut d: analysis::Diagnostic,
+) -> Cancellable<Option<lsp_types::CodeAction>> {
+    let Some(fix) = d.fix.take() else {
+        return Ok(None);
+    };
+    let edit = workspace_edit(snap, fix.source_change)?;
+    Ok(Some(lsp_types::CodeAction {
+        title: fix.label,
+        kind: Some(lsp_types::CodeActionKind::QUICKFIX),
+        diagnostics: Some(vec![diagnostic(line_index, d)]),
+        edit: Some(edit),
+        is_preferred: Some(true),
+        ..Default::default()
+    }))
+}
+
 pub fn severity(s: db::Severity) -> lsp_types::DiagnosticSeverity {
     match s {
         db::Severity::Error => lsp_types::DiagnosticSeverity::ERROR,
@@ -214,10 +239,26 @@ pub fn cancellable<R>(result: Cancellable<R>) -> Result<R, async_lsp::ResponseEr
         })
 }
 
+pub fn rename_error(err: rename::RenameError) -> async_lsp::ResponseError {
+    async_lsp::ResponseError::new(async_lsp::ErrorCode::REQUEST_FAILED, err.to_string())
+}
+
 pub fn signature_help(
     help: signature_help::SignatureHelp,
     label_offsets: bool,
 ) -> lsp_types::SignatureHelp {
+    let docs = help
+        .parameter_docs()
+        .iter()
+        .map(|docs| {
+            docs.clone().map(|value| {
+                lsp_types::Documentation::MarkupContent(lsp_types::MarkupContent {
+                    kind: lsp_types::MarkupKind::Markdown,
+                    value,
+                })
+            })
+        })
+        .collect::<Vec<_>>();
     let (label, params) = if label_offsets {
         let params = help
             .parameter_ranges()
@@ -234,18 +275,22 @@ pub fn signature_help(
                         .sum::<usize>() as u32;
                 [start, end]
             })
-            .map(|label_offsets| lsp_types::ParameterInformation {
-                label: lsp_types::ParameterLabel::LabelOffsets(label_offsets),
-                documentation: None,
-            })
+            .zip(docs)
+            .map(
+                |(label_offsets, documentation)| lsp_types::ParameterInformation {
+                    label: lsp_types::ParameterLabel::LabelOffsets(label_offsets),
+                    documentation,
+                },
+            )
             .collect::<Vec<_>>();
         (help.signature, params)
     } else {
         let params = help
             .parameter_labels()
-            .map(|label| lsp_types::ParameterInformation {
+            .zip(docs)
+            .map(|(label, documentation)| lsp_types::ParameterInformation {
                 label: lsp_types::ParameterLabel::Simple(label.to_owned()),
-                documentation: None,
+                documentation,
             })
             .collect::<Vec<_>>();
         (help.signature, params)
@@ -277,6 +322,82 @@ pub(crate) fn location(
     Ok(Some(loc))
 }
 
+/// Text edits come before the moves, since their offsets and paths refer to
+/// the files as they are now. Clients without `documentChanges` get the text
+/// edits as `changes`. Changes that move files are refused up front for
+/// clients that can't rename them, and never sent without their moves.
+pub(crate) fn workspace_edit(
+    snap: &ServerStateSnapshot,
+    change: SourceChange,
+) -> Cancellable<lsp_types::WorkspaceEdit> {
+    let (document_changes, rename_files) = {
+        let config = snap.config.read().expect("poison");
+        (
+            config.workspace_edit_document_changes(),
+            config.workspace_edit_rename_files(),
+        )
+    };
+    if !change.file_system_edits.is_empty() && !(document_changes && rename_files) {
+        tracing::error!(
+            edits = ?change.file_system_edits,
+            "client can't rename files, dropping the whole edit"
+        );
+        return Ok(lsp_types::WorkspaceEdit::default());
+    }
+    let mut text_document_edits = Vec::new();
+    let source_file_edits = change
+        .source_file_edits
+        .into_iter()
+        .sorted_by(|(a, _), (b, _)| a.cmp(b));
+    for (path, edit) in source_file_edits {
+        let Some(line_index) = snap.file_line_index(&path)? else {
+            continue;
+        };
+        let edits = edit
+            .into_iter()
+            .map(|indel| text_edit(&line_index, indel))
+            .collect::<Vec<_>>();
+        text_document_edits.push((url(&path), edits));
+    }
+    if !document_changes {
+        return Ok(lsp_types::WorkspaceEdit {
+            changes: Some(text_document_edits.into_iter().collect()),
+            document_changes: None,
+            change_annotations: None,
+        });
+    }
+    let mut operations = text_document_edits
+        .into_iter()
+        .map(|(uri, edits)| {
+            lsp_types::DocumentChangeOperation::Edit(lsp_types::TextDocumentEdit {
+                text_document: lsp_types::OptionalVersionedTextDocumentIdentifier {
+                    uri,
+                    version: None,
+                },
+                edits: edits.into_iter().map(lsp_types::OneOf::Left).collect(),
+            })
+        })
+        .collect::<Vec<_>>();
+    for edit in change.file_system_edits {
+        let op = match edit {
+            FileSystemEdit::MoveFile { src, dst } => {
+                lsp_types::ResourceOp::Rename(lsp_types::RenameFile {
+                    old_uri: url(&src),
+                    new_uri: url(&dst),
+                    options: None,
+                    annotation_id: None,
+                })
+            }
+        };
+        operations.push(lsp_types::DocumentChangeOperation::Op(op));
+    }
+    Ok(lsp_types::WorkspaceEdit {
+        changes: None,
+        document_changes: Some(lsp_types::DocumentChanges::Operations(operations)),
+        change_annotations: None,
+    })
+}
+
 pub fn url(path: &Utf8Path) -> Url {
     Url::from_file_path(path.as_std_path()).unwrap()
 }
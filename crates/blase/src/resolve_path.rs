@@ -1,63 +1,203 @@
 use camino::{Utf8Path, Utf8PathBuf};
 
 use crate::{
-    config::Config,
-    db::def::{ComponentName, LayoutName},
+    config::WorkspaceRoot,
+    db::{
+        DocumentDatabase,
+        def::{
+            ComponentName, LayoutName, ViewName,
+            class_component::{ClassComponent, Render},
+            php::PhpFile,
+            providers::{self, Registration},
+        },
+    },
 };
 
-pub(crate) fn component_paths(name: &ComponentName, config: &Config) -> (Utf8PathBuf, Utf8PathBuf) {
-    let work_path = &config.workspace_folder();
+/// The class and the template of a component are the first of their
+/// candidates that exist. Besides the application's own directories, see
+/// [`component_resources_paths`], these come from the registrations made by
+/// service providers and packages, which alone resolve namespaced components.
+pub(crate) fn component_paths(
+    db: &dyn DocumentDatabase,
+    name: &ComponentName,
+    root: &WorkspaceRoot,
+) -> (Utf8PathBuf, Utf8PathBuf) {
+    let registrations = providers::registrations(db, root);
     let path = name.path();
-    let class_path = component_class_path(path.clone(), work_path);
-    let resources_path = component_resources_path(path.clone(), work_path);
-    (class_path, resources_path)
+    let mut class_paths = Vec::new();
+    let mut resources_paths = Vec::new();
+    match name.namespace() {
+        None => {
+            let aliased = registrations
+                .iter()
+                .filter_map(|registration| match registration {
+                    Registration::Alias { name: alias, class } if alias == name.inner() => {
+                        PhpFile::find_class(db, class).map(|doc| doc.source.path(db).to_owned())
+                    }
+                    _ => None,
+                });
+            class_paths.extend(aliased);
+            for class_root in root.component_class_roots() {
+                class_paths.push(component_class_path(path.clone(), &class_root));
+            }
+            for views_root in root.component_views_roots() {
+                resources_paths.extend(component_resources_paths(path.clone(), &views_root));
+            }
+            for registration in &registrations {
+                if let Registration::AnonymousPath {
+                    prefix: None,
+                    directory,
+                } = registration
+                {
+                    resources_paths.extend(component_resources_paths(path.clone(), directory));
+                }
+            }
+        }
+        Some(namespace) => {
+            let registrations = registrations
+                .iter()
+                .filter(|registration| registration.prefix() == Some(&namespace));
+            for registration in registrations {
+                match registration {
+                    Registration::AnonymousPath { directory, .. } => {
+                        resources_paths.extend(component_resources_paths(path.clone(), directory));
+                    }
+                    Registration::ClassPath { directory, .. } => {
+                        class_paths.push(component_class_path(path.clone(), directory));
+                    }
+                    Registration::ClassNamespace { namespace, .. } => {
+                        let class = component_class_name(&path, "\\");
+                        if let Some(doc) = PhpFile::find_class(db, &format!("{namespace}\\{class}"))
+                        {
+                            class_paths.push(doc.source.path(db).to_owned());
+                        }
+                    }
+                    Registration::Alias { .. } => {}
+                }
+            }
+        }
+    }
+    (
+        first_existing(db, class_paths),
+        first_existing(db, resources_paths),
+    )
+}
+
+/// The template a component renders. For a class component this is the view
+/// its `render` method returns, or the template named after the component if
+/// `render` can't be followed. None if the class renders an inline template.
+pub(crate) fn component_template(
+    db: &dyn DocumentDatabase,
+    name: &ComponentName,
+    root: &WorkspaceRoot,
+) -> Option<Utf8PathBuf> {
+    let (class_path, resources_path) = component_paths(db, name, root);
+    component_template_at(db, &class_path, resources_path, root)
+}
+
+/// Like [`component_template`], for the paths found by [`component_paths`]
+pub(crate) fn component_template_at(
+    db: &dyn DocumentDatabase,
+    class_path: &Utf8Path,
+    resources_path: Utf8PathBuf,
+    root: &WorkspaceRoot,
+) -> Option<Utf8PathBuf> {
+    let render = db
+        .parsed_document(class_path)
+        .and_then(|class| ClassComponent::for_document(db, &class).render.clone());
+    match render {
+        Some(Render::View(view)) => Some(view_path(db, &view, root)),
+        Some(Render::Inline { .. }) => None,
+        None => Some(resources_path),
+    }
+}
+
+/// The first candidate that exists, or else the first one. Empty if there are
+/// no candidates at all.
+fn first_existing(db: &dyn DocumentDatabase, candidates: Vec<Utf8PathBuf>) -> Utf8PathBuf {
+    candidates
+        .iter()
+        .find(|path| db.source_file(path).is_some())
+        .or(candidates.first())
+        .cloned()
+        .unwrap_or_default()
+}
+
+/// Laravel looks for `<x-accordion>` in `accordion.blade.php`, then in
+/// `accordion/index.blade.php` and then in `accordion/accordion.blade.php`
+pub(crate) fn component_resources_paths(
+    path: String,
+    components_dir: &Utf8Path,
+) -> [Utf8PathBuf; 3] {
+    let sep = std::path::MAIN_SEPARATOR_STR;
+    let last = path.rsplit(sep).next().unwrap_or_default().to_owned();
+    [
+        path.clone(),
+        format!("{path}{sep}index"),
+        format!("{path}{sep}{last}"),
+    ]
+    .map(|path| components_dir.join(path + ".blade.php"))
 }
 
-fn component_resources_path(path: String, work_path: &Utf8Path) -> Utf8PathBuf {
-    work_path
-        .join(component_views_dir())
-        .join(path + ".blade.php")
+pub(crate) fn component_class_path(path: String, components_dir: &Utf8Path) -> Utf8PathBuf {
+    let class_path = component_class_name(&path, std::path::MAIN_SEPARATOR_STR);
+    components_dir.join(class_path + ".php")
 }
 
-fn component_class_path(path: String, work_path: &Utf8Path) -> Utf8PathBuf {
-    let class_path = path
-        .split(std::path::MAIN_SEPARATOR_STR)
+/// `forms/text-input` becomes `Forms<sep>TextInput`
+pub(crate) fn component_class_name(path: &str, sep: &str) -> String {
+    path.split(std::path::MAIN_SEPARATOR_STR)
         .map(|p| convert_case::ccase!(pascal, p))
         .collect::<Vec<_>>()
-        .join(std::path::MAIN_SEPARATOR_STR);
-    work_path
-        .join(component_class_dir())
-        .join(class_path + ".php")
+        .join(sep)
 }
 
-pub(crate) fn layout_paths(name: &LayoutName, config: &Config) -> (Utf8PathBuf, Utf8PathBuf) {
-    let work_path = &config.workspace_folder();
-    let class_path = layout_class_path(name, work_path);
-    let resources_path = layout_resources_path(name, work_path);
-    (class_path, resources_path)
+/// Like components, layouts are looked up in every view root, see
+/// [`WorkspaceRoot::view_roots`]
+pub(crate) fn layout_paths(
+    db: &dyn DocumentDatabase,
+    name: &LayoutName,
+    root: &WorkspaceRoot,
+) -> (Utf8PathBuf, Utf8PathBuf) {
+    let class_paths = root
+        .component_class_roots()
+        .map(|class_root| layout_class_path(name, &class_root))
+        .collect();
+    let resources_paths = root
+        .view_roots()
+        .map(|views_root| layout_resources_path(name, &views_root))
+        .collect();
+    (
+        first_existing(db, class_paths),
+        first_existing(db, resources_paths),
+    )
 }
 
-fn layout_class_path(name: &LayoutName, work_path: &Utf8Path) -> Utf8PathBuf {
+pub(crate) fn layout_class_path(name: &LayoutName, class_root: &Utf8Path) -> Utf8PathBuf {
     let layout_class_name = name.class_name();
-    work_path
-        .join(component_class_dir())
-        .join(layout_class_name + ".php")
+    class_root.join(layout_class_name + ".php")
 }
 
-fn layout_resources_path(name: &LayoutName, work_path: &Utf8Path) -> Utf8PathBuf {
-    let path = match name {
-        LayoutName::Default => work_path
-            .join(component_views_dir())
-            .join("layout.blade.php"),
+pub(crate) fn layout_resources_path(name: &LayoutName, views_root: &Utf8Path) -> Utf8PathBuf {
+    match name {
+        LayoutName::Default => views_root.join("components").join("layout.blade.php"),
         LayoutName::Name(name) => {
             let template_path = format!("{}.blade.php", name);
-            let path = Utf8PathBuf::from(views_dir())
-                .join("layouts")
-                .join(template_path);
-            work_path.join(path)
+            views_root.join("layouts").join(template_path)
         }
-    };
-    path
+    }
+}
+
+pub(crate) fn view_path(
+    db: &dyn DocumentDatabase,
+    name: &ViewName,
+    root: &WorkspaceRoot,
+) -> Utf8PathBuf {
+    let candidates = root
+        .view_roots()
+        .map(|views_root| views_root.join(name.path() + ".blade.php"))
+        .collect();
+    first_existing(db, candidates)
 }
 
 pub fn component_views_dir() -> String {
@@ -68,6 +208,23 @@ pub fn views_dir() -> String {
     "resources/views".to_string()
 }
 
+pub fn app_dir() -> String {
+    "app".to_string()
+}
+
 pub fn component_class_dir() -> String {
     "app/View/Components".to_string()
 }
+
+pub fn providers_dir() -> String {
+    "app/Providers".to_string()
+}
+
+pub fn vendor_dir() -> String {
+    "vendor".to_string()
+}
+
+/// Where packages conventionally keep their class components
+pub fn package_component_class_dir() -> String {
+    "src/View/Components".to_string()
+}
@@ -1,4 +1,5 @@
 use std::{
+    collections::HashSet,
     sync::{Arc, RwLock},
     thread,
 };
@@ -7,7 +8,7 @@ use async_lsp::{
     ClientSocket, LanguageClient,
     client_monitor::ClientProcessMonitorLayer,
     concurrency::ConcurrencyLayer,
-    lsp_types::{self, ClientCapabilities, ProgressParamsValue},
+    lsp_types::{self, ClientCapabilities, LSPAny, ProgressParamsValue},
     panic::CatchUnwindBuilder,
     router::Router,
     server::LifecycleLayer,
@@ -21,7 +22,7 @@ use tower::ServiceBuilder;
 
 use crate::{
     analysis::{Analysis, AnalysisHost, Cancellable},
-    config::Config,
+    config::{Config, WorkspaceRoot},
     db::SourceDatabase,
     document_data::DocumentData,
     handler,
@@ -101,6 +102,15 @@ pub fn run_server(
             .request::<lsp_types::request::GotoDefinition, _>(wrap_responder!(
                 handler::request::handle_goto_def
             ))
+            .request::<lsp_types::request::PrepareRenameRequest, _>(wrap_responder!(
+                handler::request::handle_prepare_rename
+            ))
+            .request::<lsp_types::request::Rename, _>(wrap_responder!(
+                handler::request::handle_rename
+            ))
+            .request::<lsp_types::request::CodeActionRequest, _>(wrap_responder!(
+                handler::request::handle_code_action
+            ))
             .request::<lsp_types::request::Initialize, _>(|state, params| {
                 handler::request::handle_initialize(state, params)
             });
@@ -121,6 +131,15 @@ pub fn run_server(
             )
             .notification::<lsp_types::notification::DidSaveTextDocument>(
                 handler::notification::handle_did_save,
+            )
+            .notification::<lsp_types::notification::DidChangeConfiguration>(
+                handler::notification::handle_did_change_configuration,
+            )
+            .notification::<lsp_types::notification::DidChangeWatchedFiles>(
+                handler::notification::handle_did_change_watched_files,
+            )
+            .notification::<lsp_types::notification::DidChangeWorkspaceFolders>(
+                handler::notification::handle_did_change_workspace_folders,
             );
 
         ServiceBuilder::new()
@@ -138,20 +157,28 @@ pub struct ServerState {
     pub config: Arc<RwLock<Config>>,
     pub client: ClientSocket,
     pub documents: Arc<DashMap<Utf8PathBuf, DocumentData>>,
+    /// Files the client shows diagnostics for, which may have been closed since
+    pub diagnosed: HashSet<Utf8PathBuf>,
     pub analysis_host: AnalysisHost,
 }
 
 impl ServerState {
     pub fn new(client: ClientSocket) -> Self {
         let current_dir = std::env::current_dir().expect("cannot access current directory");
+        let folder = Utf8PathBuf::from_path_buf(current_dir).unwrap();
         let config = Config {
             capabilities: ClientCapabilities::default(),
-            workspace_folder: Utf8PathBuf::from_path_buf(current_dir).unwrap(),
+            roots: vec![WorkspaceRoot::new(
+                folder.file_name().unwrap_or_default().to_string(),
+                folder,
+            )],
             client_info: None,
+            settings: LSPAny::Null,
         };
         Self {
             client,
             documents: Arc::new(DashMap::new()),
+            diagnosed: HashSet::new(),
             config: Arc::new(RwLock::new(config)),
             analysis_host: AnalysisHost::default(),
         }
@@ -206,9 +233,13 @@ impl ServerStateSnapshot {
         self.documents.get(uri)
     }
 
-    pub fn workspace_folder(&self) -> Utf8PathBuf {
+    pub fn workspace_folders(&self) -> Vec<Utf8PathBuf> {
         let config = self.config.read().expect("poison");
-        config.workspace_folder()
+        config
+            .roots
+            .iter()
+            .map(|root| root.folder.clone())
+            .collect()
     }
 
     pub(crate) fn file_line_index(
@@ -1,10 +1,14 @@
 use async_lsp::ClientSocket;
 use async_lsp::lsp_types::{
-    ClientCapabilities, DidChangeTextDocumentParams, DidOpenTextDocumentParams, InitializeParams,
-    Position, TextDocumentContentChangeEvent, TextDocumentItem, Url,
-    VersionedTextDocumentIdentifier, WorkspaceFolder,
+    ClientCapabilities, DidChangeConfigurationParams, DidChangeTextDocumentParams,
+    DidChangeWatchedFilesParams, DidChangeWorkspaceFoldersParams, DidCloseTextDocumentParams,
+    DidOpenTextDocumentParams, FileChangeType, FileEvent, InitializeParams, LSPAny, Position,
+    TextDocumentContentChangeEvent, TextDocumentIdentifier, TextDocumentItem, Url,
+    VersionedTextDocumentIdentifier, WorkspaceFolder, WorkspaceFoldersChangeEvent,
 };
 
+use blase::config::{ProjectLayout, WorkspaceRoot};
+use blase::db::SourceDatabase;
 use blase::document_data::DocumentData;
 use blase::handler;
 use blase::server::ServerState;
@@ -54,8 +58,73 @@ fn test_handle_initialize_with_workspace_folder() {
 }
 
 #[test]
-fn test_handle_initialize_multiple_workspaces_returns_error() {
+fn test_handle_initialize_reads_project_layout() {
     let mut server = create_test_server();
+    let options = r#"{
+        "viewRoots": ["modules/admin/resources/views", "resources/views"],
+        "namespaces": { "admin": "modules/admin/components" }
+    }"#;
+    let params = InitializeParams {
+        workspace_folders: Some(vec![WorkspaceFolder {
+            uri: create_test_url("/test/workspace"),
+            name: "test".to_string(),
+        }]),
+        initialization_options: Some(options.parse::<LSPAny>().unwrap()),
+        capabilities: ClientCapabilities::default(),
+        ..Default::default()
+    };
+
+    let result =
+        futures::executor::block_on(handler::request::handle_initialize(&mut server, params));
+
+    assert!(result.is_ok());
+    let config = server.config.read().unwrap();
+    assert_eq!(
+        config.roots[0].layout().view_roots,
+        [
+            Utf8PathBuf::from("modules/admin/resources/views"),
+            Utf8PathBuf::from("resources/views"),
+        ]
+    );
+    assert_eq!(
+        config.roots[0].layout().component_class_roots,
+        ProjectLayout::default().component_class_roots
+    );
+    assert_eq!(
+        config.roots[0].layout().namespaces,
+        [(
+            smol_str::SmolStr::new("admin"),
+            Utf8PathBuf::from("modules/admin/components")
+        )]
+    );
+}
+
+#[test]
+fn test_handle_did_change_configuration_updates_project_layout() {
+    let mut server = create_test_server();
+    let settings = r#"{ "blase": { "componentClassRoots": ["modules/Admin/View/Components"] } }"#;
+    let params = DidChangeConfigurationParams {
+        settings: settings.parse::<LSPAny>().unwrap(),
+    };
+
+    let _ = handler::notification::handle_did_change_configuration(&mut server, params);
+
+    let config = server.config.read().unwrap();
+    assert_eq!(
+        config.roots[0].layout().component_class_roots,
+        [Utf8PathBuf::from("modules/Admin/View/Components")]
+    );
+    assert_eq!(
+        config.roots[0].layout().view_roots,
+        ProjectLayout::default().view_roots
+    );
+}
+
+#[test]
+fn test_handle_initialize_multiple_workspaces() {
+    let mut server = create_test_server();
+    let options =
+        r#"{ "folders": { "workspace2": { "componentClassRoots": ["src/View/Components"] } } }"#;
     let params = InitializeParams {
         workspace_folders: Some(vec![
             WorkspaceFolder {
@@ -67,6 +136,7 @@ fn test_handle_initialize_multiple_workspaces_returns_error() {
                 name: "workspace2".to_string(),
             },
         ]),
+        initialization_options: Some(options.parse::<LSPAny>().unwrap()),
         capabilities: ClientCapabilities::default(),
         ..Default::default()
     };
@@ -74,7 +144,56 @@ fn test_handle_initialize_multiple_workspaces_returns_error() {
     let result =
         futures::executor::block_on(handler::request::handle_initialize(&mut server, params));
 
-    assert!(result.is_err());
+    assert!(result.is_ok());
+    let config = server.config.read().unwrap();
+    assert_eq!(config.roots.len(), 2);
+    assert_eq!(config.roots[0].folder, create_test_path("/test/workspace1"));
+    assert_eq!(*config.roots[0].layout(), ProjectLayout::default());
+    assert_eq!(config.roots[1].folder, create_test_path("/test/workspace2"));
+    assert_eq!(
+        config.roots[1].layout().component_class_roots,
+        [Utf8PathBuf::from("src/View/Components")]
+    );
+    let file = create_test_path("/test/workspace2/resources/views/index.blade.php");
+    assert_eq!(config.root_for(&file).name, "workspace2");
+}
+
+#[test]
+fn test_handle_did_change_workspace_folders_updates_roots() {
+    let mut server = create_test_server();
+    let params = InitializeParams {
+        workspace_folders: Some(vec![WorkspaceFolder {
+            uri: create_test_url("/test/workspace1"),
+            name: "workspace1".to_string(),
+        }]),
+        capabilities: ClientCapabilities::default(),
+        ..Default::default()
+    };
+    let _ = futures::executor::block_on(handler::request::handle_initialize(&mut server, params));
+    let stale = create_test_path("/test/workspace1/resources/views/index.blade.php");
+    server.analysis_host.set_source_file(stale, "Hello World");
+
+    let params = DidChangeWorkspaceFoldersParams {
+        event: WorkspaceFoldersChangeEvent {
+            added: vec![WorkspaceFolder {
+                uri: create_test_url("/test/workspace2"),
+                name: "workspace2".to_string(),
+            }],
+            removed: vec![WorkspaceFolder {
+                uri: create_test_url("/test/workspace1"),
+                name: "workspace1".to_string(),
+            }],
+        },
+    };
+    let _ = handler::notification::handle_did_change_workspace_folders(&mut server, params);
+
+    let config = server.config.read().unwrap();
+    assert_eq!(config.roots.len(), 1);
+    assert_eq!(config.roots[0].name, "workspace2");
+    assert_eq!(config.roots[0].folder, create_test_path("/test/workspace2"));
+    drop(config);
+    let analysis = server.snapshot().analysis;
+    assert_eq!(analysis.raw_database().files_count(), 0);
 }
 
 #[test]
@@ -98,6 +217,54 @@ fn test_handle_did_open_inserts_document() {
     assert_eq!(doc.contents, "Hello World");
 }
 
+#[test]
+fn test_handle_did_open_sets_source_file() {
+    let mut server = create_test_server();
+    let url = create_test_url("/test/modules/admin/panel.blade.php");
+    let path = create_test_path("/test/modules/admin/panel.blade.php");
+    server
+        .analysis_host
+        .set_source_file(path.clone(), "Saved on disk");
+    let params = DidOpenTextDocumentParams {
+        text_document: TextDocumentItem {
+            uri: url,
+            language_id: "blade".to_string(),
+            version: 1,
+            text: "Unsaved buffer".to_string(),
+        },
+    };
+
+    let _ = handler::notification::handle_did_open(&mut server, params);
+
+    let analysis = server.snapshot().analysis;
+    let db = analysis.raw_database();
+    assert_eq!(db.files_count(), 1);
+    assert_eq!(db.contents(&path).as_deref(), Some("Unsaved buffer"));
+}
+
+#[test]
+fn test_handle_did_close_removes_unsaved_file() {
+    let mut server = create_test_server();
+    let url = create_test_url("/test/modules/admin/panel.blade.php");
+    let params = DidOpenTextDocumentParams {
+        text_document: TextDocumentItem {
+            uri: url.clone(),
+            language_id: "blade".to_string(),
+            version: 1,
+            text: "Never saved".to_string(),
+        },
+    };
+    let _ = handler::notification::handle_did_open(&mut server, params);
+
+    let params = DidCloseTextDocumentParams {
+        text_document: TextDocumentIdentifier { uri: url },
+    };
+    let _ = handler::notification::handle_did_close(&mut server, params);
+
+    let analysis = server.snapshot().analysis;
+    assert_eq!(analysis.raw_database().files_count(), 0);
+}
+
 #[test]
 fn test_handle_did_change_updates_document() {
     let mut server = create_test_server();
@@ -181,3 +348,157 @@ fn test_analysis_host_can_load_files() {
         "analysis host should have one file after set_source_file"
     );
 }
+
+#[test]
+fn test_handle_did_change_watched_files_adds_and_removes_files() {
+    let mut server = create_test_server();
+    let dir = std::env::temp_dir().join("blase_watched_files");
+    std::fs::create_dir_all(&dir).unwrap();
+    let path = Utf8PathBuf::from_path_buf(dir.join("alert.blade.php")).unwrap();
+    std::fs::write(&path, "<div>{{ $slot }}</div>").unwrap();
+    let uri = Url::from_file_path(&path).unwrap();
+
+    let params = DidChangeWatchedFilesParams {
+        changes: vec![FileEvent::new(uri.clone(), FileChangeType::CREATED)],
+    };
+    let _ = handler::notification::handle_did_change_watched_files(&mut server, params);
+
+    let analysis = server.snapshot().analysis;
+    assert_eq!(analysis.raw_database().files_count(), 1);
+    drop(analysis);
+
+    std::fs::remove_file(&path).unwrap();
+    let params = DidChangeWatchedFilesParams {
+        changes: vec![FileEvent::new(uri, FileChangeType::DELETED)],
+    };
+    let _ = handler::notification::handle_did_change_watched_files(&mut server, params);
+
+    let analysis = server.snapshot().analysis;
+    assert_eq!(analysis.raw_database().files_count(), 0);
+}
+
+#[test]
+fn test_handle_did_change_watched_files_ignores_open_documents() {
+    let mut server = create_test_server();
+    let url = create_test_url("/test/file.blade.php");
+    let path = create_test_path("/test/file.blade.php");
+    server.documents.insert(
+        path.clone(),
+        DocumentData {
+            contents: "Hello World".to_string(),
+        },
+    );
+    server.analysis_host.set_source_file(path, "Hello World");
+
+    let params = DidChangeWatchedFilesParams {
+        changes: vec![FileEvent::new(url, FileChangeType::DELETED)],
+    };
+    let _ = handler::notification::handle_did_change_watched_files(&mut server, params);
+
+    let analysis = server.snapshot().analysis;
+    assert_eq!(analysis.raw_database().files_count(), 1);
+}
+
+#[test]
+fn test_handle_did_change_watched_files_reports_missing_components() {
+    let mut server = create_test_server();
+    let dir = std::env::temp_dir().join("blase_watched_dependents");
+    let dir = Utf8PathBuf::from_path_buf(dir).unwrap();
+    let components = dir.join("resources/views/components");
+    std::fs::create_dir_all(&components).unwrap();
+    let alert = components.join("alert.blade.php");
+    let index = dir.join("resources/views/index.blade.php");
+    std::fs::write(&alert, "<div>{{ $slot }}</div>").unwrap();
+    std::fs::write(&index, "<x-alert>Saved</x-alert>").unwrap();
+    server.config.write().unwrap().roots = vec![WorkspaceRoot::new("test".to_string(), dir)];
+    server
+        .analysis_host
+        .set_source_file(alert.clone(), "<div>{{ $slot }}</div>");
+    server
+        .analysis_host
+        .set_source_file(index.clone(), "<x-alert>Saved</x-alert>");
+
+    let diagnostics = |server: &ServerState| {
+        let snap = server.snapshot();
+        let config = snap.config.read().unwrap();
+        snap.analysis
+            .full_diagnostics(&config, &index)
+            .unwrap()
+            .into_iter()
+            .map(|diagnostic| diagnostic.message)
+            .collect::<Vec<_>>()
+    };
+    assert!(diagnostics(&server).is_empty());
+
+    std::fs::remove_file(&alert).unwrap();
+    let params = DidChangeWatchedFilesParams {
+        changes: vec![FileEvent::new(
+            Url::from_file_path(&alert).unwrap(),
+            FileChangeType::DELETED,
+        )],
+    };
+    let _ = handler::notification::handle_did_change_watched_files(&mut server, params);
+
+    assert_eq!(
+        diagnostics(&server),
+        ["cannot find component `x-alert` in the current workspace"]
+    );
+}
+
+#[test]
+fn test_handle_did_change_watched_files_picks_up_new_module_directories() {
+    let mut server = create_test_server();
+    let dir = std::env::temp_dir().join("blase_watched_modules");
+    let _ = std::fs::remove_dir_all(&dir);
+    let dir = Utf8PathBuf::from_path_buf(dir).unwrap();
+    let index = dir.join("modules/billing/resources/views/index.blade.php");
+    std::fs::create_dir_all(index.parent().unwrap()).unwrap();
+    std::fs::write(&index, "<x-invoice/>").unwrap();
+    let root = WorkspaceRoot::new("test".to_string(), dir.clone()).with_layout(ProjectLayout {
+        view_roots: vec![Utf8PathBuf::from("modules/*/resources/views")],
+        ..Default::default()
+    });
+    server.config.write().unwrap().roots = vec![root];
+    server
+        .analysis_host
+        .set_source_file(index.clone(), "<x-invoice/>");
+
+    let watchers = blase::capabilities::file_watchers(&server.config.read().unwrap()).watchers;
+    assert!(watchers.iter().any(|watcher| {
+        watcher.glob_pattern
+            == async_lsp::lsp_types::GlobPattern::String(format!("{dir}/**/*.blade.php"))
+    }));
+
+    let diagnostics = |server: &ServerState| {
+        let snap = server.snapshot();
+        let config = snap.config.read().unwrap();
+        snap.analysis
+            .full_diagnostics(&config, &index)
+            .unwrap()
+            .into_iter()
+            .map(|diagnostic| diagnostic.message)
+            .collect::<Vec<_>>()
+    };
+    assert_eq!(
+        diagnostics(&server),
+        ["cannot find component `x-invoice` in the current workspace"]
+    );
+
+    // A module created after the layout was read
+    let invoice = dir.join("modules/shop/resources/views/components/invoice.blade.php");
+    std::fs::create_dir_all(invoice.parent().unwrap()).unwrap();
+    std::fs::write(&invoice, "<div>{{ $slot }}</div>").unwrap();
+    let params = DidChangeWatchedFilesParams {
+        changes: vec![FileEvent::new(
+            Url::from_file_path(&invoice).unwrap(),
+            FileChangeType::CREATED,
+        )],
+    };
+    let _ = handler::notification::handle_did_change_watched_files(&mut server, params);
+
+    let view_roots = server.config.read().unwrap().roots[0]
+        .view_roots()
+        .collect::<Vec<_>>();
+    assert!(view_roots.contains(&dir.join("modules/shop/resources/views")));
+    assert!(diagnostics(&server).is_empty());
+}
@@ -22,8 +22,20 @@ async function startClient() {
 		command: execPath,
 	};
 
+	const config = vscode.workspace.getConfiguration('blase');
 	const clientOptions: LanguageClientOptions = {
-		documentSelector: [{ scheme: 'file', language: 'blade' }]
+		documentSelector: [
+			{ scheme: 'file', language: 'blade' },
+			// Class components may write their template inline in `render()`
+			...componentClassRoots(config).map((root) => (
+				{ scheme: 'file', language: 'php', pattern: `**/${root}/**` }
+			)),
+		],
+		initializationOptions: config,
+		synchronize: {
+			// Sent as `workspace/didChangeConfiguration`, so the server re-indexes
+			configurationSection: 'blase',
+		},
 	};
 
 	client = new LanguageClient('blase', 'Blase', serverExecutable, clientOptions);
@@ -31,6 +43,16 @@ async function startClient() {
 	await client.start();
 }
 
+/** The directories of class components, including those of single folders */
+function componentClassRoots(config: vscode.WorkspaceConfiguration): string[] {
+	const folders = config.get<Record<string, { componentClassRoots?: string[] }>>('folders') ?? {};
+	const roots = [
+		...config.get<string[]>('componentClassRoots', ['app/View/Components']),
+		...Object.values(folders).flatMap((folder) => folder.componentClassRoots ?? []),
+	];
+	return [...new Set(roots)];
+}
+
 export async function activate(context: vscode.ExtensionContext) {
 	log.appendLine('Blase activation event');
 
@@ -40,6 +62,13 @@ export async function activate(context: vscode.ExtensionContext) {
 		context.subscriptions.push(vscode.commands.registerCommand(command, handler));
 	}
 
+	// Which PHP files the client sends depends on the class component roots
+	context.subscriptions.push(vscode.workspace.onDidChangeConfiguration(async (event) => {
+		if (event.affectsConfiguration('blase.componentClassRoots') || event.affectsConfiguration('blase.folders')) {
+			await restartClient();
+		}
+	}));
+
 	await startClient();
 }
 